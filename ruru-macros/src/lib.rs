@@ -0,0 +1,419 @@
+//! Procedural macros for defining Ruby classes backed by Rust structs
+//!
+//! `#[ruby_class]` marks a struct which is stored inside Ruby objects, `#[ruby_methods]`
+//! exposes public functions of its `impl` block as Ruby methods and generates an `init()`
+//! function which defines the class.
+//!
+//! - Functions with `&self` or `&mut self` receivers become instance methods;
+//! - Functions without a receiver become class methods. If such function returns `Self`,
+//!   the result is wrapped into a new instance of the class (use it for `new`);
+//! - Only `pub` functions are exposed to Ruby, other functions stay ordinary Rust helpers;
+//! - Arguments can have any type implementing `VerifiedObject`, a `TypeError` is raised if an
+//!   argument has another type and an `ArgumentError` if the number of arguments is wrong;
+//! - The return type must implement `Object` (or be `Self`), functions without a return type
+//!   return `nil`;
+//! - Panics are raised as `RuntimeError`, as well as calls of a method which re-enter
+//!   the instance while a `&mut self` method is running;
+//! - `#[ruby(name = "empty?")]` sets a Ruby name which is not a valid Rust identifier.
+//!
+//! # Examples
+//!
+//! ```
+//! extern crate ruru;
+//! extern crate ruru_macros;
+//!
+//! use ruru::{Boolean, Class, Fixnum, Object, VM};
+//! use ruru_macros::{ruby_class, ruby_methods};
+//!
+//! #[ruby_class("Counter")]
+//! pub struct Counter {
+//!     count: i64,
+//! }
+//!
+//! #[ruby_methods]
+//! impl Counter {
+//!     /// Creates a new counter starting from `start`
+//!     pub fn new(start: Fixnum) -> Self {
+//!         Counter { count: start.to_i64() }
+//!     }
+//!
+//!     pub fn increment(&mut self, step: Fixnum) -> Fixnum {
+//!         self.count += step.to_i64();
+//!
+//!         self.current()
+//!     }
+//!
+//!     #[ruby(name = "zero?")]
+//!     pub fn is_zero(&self) -> Boolean {
+//!         Boolean::new(self.count == 0)
+//!     }
+//!
+//!     fn current(&self) -> Fixnum {
+//!         Fixnum::new(self.count)
+//!     }
+//! }
+//!
+//! fn main() {
+//!     # VM::init();
+//!     Counter::init();
+//!
+//!     let counter_class = Class::from_existing("Counter");
+//!     let counter = counter_class.send("new", vec![Fixnum::new(1).to_any_object()]);
+//!
+//!     let count = counter.send("increment", vec![Fixnum::new(2).to_any_object()]);
+//!
+//!     assert_eq!(count.try_convert_to::<Fixnum>(), Ok(Fixnum::new(3)));
+//!     assert!(!counter.send("zero?", vec![]).try_convert_to::<Boolean>().unwrap().to_bool());
+//! }
+//! ```
+//!
+//! Ruby:
+//!
+//! ```ruby
+//! class Counter
+//!   def initialize(start)
+//!     @count = start
+//!   end
+//!
+//!   def increment(step)
+//!     @count += step
+//!   end
+//!
+//!   def zero?
+//!     @count.zero?
+//!   end
+//! end
+//!
+//! counter = Counter.new(1)
+//!
+//! counter.increment(2) == 3
+//! counter.zero? == false
+//! ```
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Error, FnArg, ImplItem, ImplItemFn, ItemImpl, ItemStruct, LitStr,
+          ReceiverKind, ReturnType, Type, Visibility};
+
+/// Marks a struct which is wrapped into instances of a Ruby class
+///
+/// The name of the Ruby class is given as an argument (`#[ruby_class("Counter")]`),
+/// the name of the struct is used by default.
+///
+/// See crate documentation for an example.
+#[proc_macro_attribute]
+pub fn ruby_class(arguments: TokenStream, input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as ItemStruct);
+
+    let class_name = if arguments.is_empty() {
+        LitStr::new(&item.ident.to_string(), item.ident.span())
+    } else {
+        parse_macro_input!(arguments as LitStr)
+    };
+
+    if !item.generics.params.is_empty() {
+        return Error::new_spanned(&item.generics, "Ruby classes cannot have generic parameters")
+            .to_compile_error()
+            .into();
+    }
+
+    let ident = &item.ident;
+
+    let expanded = quote! {
+        #item
+
+        impl #ident {
+            #[doc(hidden)]
+            pub const __RURU_CLASS_NAME: &'static str = #class_name;
+        }
+    };
+
+    expanded.into()
+}
+
+/// Exposes public functions of an `impl` block of a `#[ruby_class]` struct as Ruby methods
+///
+/// Generates `init()` function which defines the Ruby class and its methods.
+///
+/// See crate documentation for an example.
+#[proc_macro_attribute]
+pub fn ruby_methods(_arguments: TokenStream, input: TokenStream) -> TokenStream {
+    let mut item = parse_macro_input!(input as ItemImpl);
+
+    match expand_methods(&mut item) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_methods(item: &mut ItemImpl) -> syn::Result<TokenStream2> {
+    if let Some((ref path, _)) = item.trait_ {
+        return Err(Error::new_spanned(path, "#[ruby_methods] cannot be used on trait impls"));
+    }
+
+    if !item.generics.params.is_empty() {
+        return Err(Error::new_spanned(&item.generics,
+                                      "#[ruby_methods] cannot be used on generic impls"));
+    }
+
+    let self_ty = item.self_ty.clone();
+    let mut callbacks = Vec::new();
+    let mut registrations = Vec::new();
+
+    for impl_item in item.items.iter_mut() {
+        if let ImplItem::Fn(ref mut method) = *impl_item {
+            let ruby_name = take_ruby_name(method)?;
+
+            if let Visibility::Public(_) = method.vis {
+                let method = Method::new(method, ruby_name, &self_ty)?;
+
+                callbacks.push(method.callback(&self_ty));
+                registrations.push(method.registration());
+            }
+        }
+    }
+
+    Ok(quote! {
+        #item
+
+        impl #self_ty {
+            /// Defines the Ruby class and its methods
+            pub fn init() {
+                #(#callbacks)*
+
+                let mut class = ::ruru::Class::new(<#self_ty>::__RURU_CLASS_NAME, None);
+
+                #(#registrations)*
+            }
+        }
+    })
+}
+
+// Removes `#[ruby(...)]` attributes and returns the name given with `#[ruby(name = "...")]`
+fn take_ruby_name(method: &mut ImplItemFn) -> syn::Result<Option<LitStr>> {
+    let mut ruby_name = None;
+    let mut result = Ok(());
+
+    method.attrs.retain(|attribute| {
+        if !attribute.path().is_ident("ruby") {
+            return true;
+        }
+
+        let parsed = attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                ruby_name = Some(meta.value()?.parse::<LitStr>()?);
+
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute, expected `name = \"...\"`"))
+            }
+        });
+
+        if let Err(error) = parsed {
+            result = Err(error);
+        }
+
+        false
+    });
+
+    result.map(|_| ruby_name)
+}
+
+enum Receiver {
+    None,
+    Shared,
+    Mutable,
+}
+
+struct Method {
+    ident: Ident,
+    ruby_name: LitStr,
+    receiver: Receiver,
+    argument_types: Vec<Type>,
+    returns_self: bool,
+    returns_nil: bool,
+}
+
+impl Method {
+    fn new(method: &ImplItemFn, ruby_name: Option<LitStr>, self_ty: &Type) -> syn::Result<Self> {
+        let signature = &method.sig;
+
+        if !signature.generics.params.is_empty() {
+            return Err(Error::new_spanned(&signature.generics,
+                                          "Ruby methods cannot have generic parameters"));
+        }
+
+        let mut receiver = Receiver::None;
+        let mut argument_types = Vec::new();
+
+        for input in signature.inputs.iter() {
+            match *input {
+                FnArg::Receiver(ref self_argument) => {
+                    receiver = match self_argument.kind {
+                        ReceiverKind::Reference(_, _, None) => Receiver::Shared,
+                        ReceiverKind::Reference(_, _, Some(_)) => Receiver::Mutable,
+                        _ => {
+                            return Err(Error::new_spanned(self_argument,
+                                                          "Ruby methods must receive `&self` \
+                                                           or `&mut self`"))
+                        }
+                    };
+                }
+                FnArg::Typed(ref argument) => argument_types.push((*argument.ty).clone()),
+            }
+        }
+
+        let (returns_self, returns_nil) = match signature.output {
+            ReturnType::Default => (false, true),
+            ReturnType::Type(_, ref ty) => (is_self_type(ty, self_ty), false),
+        };
+
+        let ident = signature.ident.clone();
+        let ruby_name = ruby_name.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+
+        Ok(Method {
+            ident,
+            ruby_name,
+            receiver,
+            argument_types,
+            returns_self,
+            returns_nil,
+        })
+    }
+
+    fn callback_ident(&self) -> Ident {
+        format_ident!("__ruru_callback_{}", self.ident)
+    }
+
+    fn callback(&self, self_ty: &Type) -> TokenStream2 {
+        let callback_ident = self.callback_ident();
+        let method_ident = &self.ident;
+        let arity = self.argument_types.len();
+        let argument_types = &self.argument_types;
+
+        let argument_idents = (0..arity)
+            .map(|index| Ident::new(&format!("argument_{}", index), Span::call_site()))
+            .collect::<Vec<_>>();
+
+        let conversions = argument_types.iter()
+            .enumerate()
+            .map(|(index, ty)| {
+                quote! {
+                    ::ruru::Object::try_convert_to::<#ty>(&arguments[#index])?
+                }
+            });
+
+        // Instance methods borrow the data with `VM::with_data()` or `VM::with_data_mut()`,
+        // which raise if the method is re-entered while the data is mutably borrowed
+        let call = match self.receiver {
+            Receiver::None => {
+                quote! {
+                    ::ruru::VM::raise_on_panic(|| <#self_ty>::#method_ident(#(#argument_idents),*))
+                }
+            }
+            Receiver::Shared => {
+                quote! {
+                    ::ruru::VM::with_data(&itself, |data: &#self_ty| {
+                        <#self_ty>::#method_ident(data, #(#argument_idents),*)
+                    })
+                }
+            }
+            Receiver::Mutable => {
+                quote! {
+                    ::ruru::VM::with_data_mut(&itself, |data: &mut #self_ty| {
+                        <#self_ty>::#method_ident(data, #(#argument_idents),*)
+                    })
+                }
+            }
+        };
+
+        let result = if self.returns_nil {
+            quote! {
+                #call;
+
+                ::ruru::Object::to_any_object(&::ruru::NilClass::new())
+            }
+        } else if self.returns_self {
+            // Class methods wrap the data into an instance of the receiver (which can be
+            // a subclass), instance methods use the class of the receiver
+            let class = match self.receiver {
+                Receiver::None => quote! { unsafe { ::ruru::Object::to::<::ruru::Class>(&itself) } },
+                _ => quote! { ::ruru::Object::class(&itself) },
+            };
+
+            quote! {
+                let result = #call;
+                let class = #class;
+
+                class.wrap_data(result)
+            }
+        } else {
+            quote! {
+                let result = #call;
+
+                ::ruru::Object::to_any_object(&result)
+            }
+        };
+
+        quote! {
+            extern "C" fn #callback_ident(argc: ::ruru::types::Argc,
+                                          argv: *const ::ruru::AnyObject,
+                                          itself: ::ruru::AnyObject)
+                                          -> ::ruru::AnyObject {
+                // Raising does not run destructors of Rust values, so the arguments are
+                // converted and dropped before an error is raised
+                let converted = {
+                    let arguments = ::ruru::VM::parse_arguments(argc, argv);
+
+                    let convert = || -> ::std::result::Result<(#(#argument_types,)*),
+                                                         ::ruru::result::Error> {
+                        if arguments.len() != #arity {
+                            return Err(::ruru::result::Error::ArgumentError(
+                                format!("wrong number of arguments (given {}, expected {})",
+                                        arguments.len(),
+                                        #arity)
+                            ));
+                        }
+
+                        Ok((#(#conversions,)*))
+                    };
+
+                    convert().map_err(|error| {
+                        ::ruru::Exception::new(&error.to_exception(), &error.to_string())
+                    })
+                };
+
+                let (#(#argument_idents,)*) = match converted {
+                    Ok(arguments) => arguments,
+                    Err(exception) => exception.raise(),
+                };
+
+                #result
+            }
+        }
+    }
+
+    fn registration(&self) -> TokenStream2 {
+        let callback_ident = self.callback_ident();
+        let ruby_name = &self.ruby_name;
+
+        match self.receiver {
+            Receiver::None => quote! {
+                ::ruru::Object::def_self(&mut class, #ruby_name, #callback_ident);
+            },
+            _ => quote! {
+                ::ruru::Object::def(&mut class, #ruby_name, #callback_ident);
+            },
+        }
+    }
+}
+
+fn is_self_type(ty: &Type, self_ty: &Type) -> bool {
+    match *ty {
+        Type::Path(ref path) if path.qself.is_none() && path.path.is_ident("Self") => true,
+        _ => quote!(#ty).to_string() == quote!(#self_ty).to_string(),
+    }
+}
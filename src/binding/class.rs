@@ -1,11 +1,30 @@
 use ruby_sys::class;
 
 use binding::util as binding_util;
-use types::{Argc, Callback, CallbackPtr, Value};
+use types::{Argc, c_char, Callback, CallbackPtr, Value};
 use util;
 
 use Object;
 
+extern "C" {
+    fn rb_define_alias(klass: Value, new_name: *const c_char, old_name: *const c_char);
+    fn rb_define_module_function(module: Value,
+                                 name: *const c_char,
+                                 callback: CallbackPtr,
+                                 argc: Argc);
+    fn rb_define_private_method(klass: Value,
+                                name: *const c_char,
+                                callback: CallbackPtr,
+                                argc: Argc);
+    fn rb_define_protected_method(klass: Value,
+                                  name: *const c_char,
+                                  callback: CallbackPtr,
+                                  argc: Argc);
+    fn rb_obj_is_kind_of(object: Value, klass: Value) -> Value;
+    fn rb_remove_method(klass: Value, name: *const c_char);
+    fn rb_undef_method(klass: Value, name: *const c_char);
+}
+
 pub fn define_class(name: &str, superclass: Value) -> Value {
     let name = util::str_to_cstring(name);
 
@@ -30,6 +49,10 @@ pub fn singleton_class(object: Value) -> Value {
     unsafe { class::rb_singleton_class(object) }
 }
 
+pub fn is_kind_of(object: Value, klass: Value) -> bool {
+    unsafe { rb_obj_is_kind_of(object, klass).is_true() }
+}
+
 pub fn ancestors(klass: Value) -> Value {
     unsafe { class::rb_mod_ancestors(klass) }
 }
@@ -77,3 +100,77 @@ pub fn define_singleton_method<I: Object, O: Object>(klass: Value,
         class::rb_define_singleton_method(klass, name.as_ptr(), callback as CallbackPtr, -1);
     }
 }
+
+pub fn define_fixed_method(klass: Value, name: &str, callback: CallbackPtr, arity: Argc) {
+    let name = util::str_to_cstring(name);
+
+    unsafe {
+        class::rb_define_method(klass, name.as_ptr(), callback, arity);
+    }
+}
+
+pub fn define_fixed_singleton_method(klass: Value,
+                                     name: &str,
+                                     callback: CallbackPtr,
+                                     arity: Argc) {
+    let name = util::str_to_cstring(name);
+
+    unsafe {
+        class::rb_define_singleton_method(klass, name.as_ptr(), callback, arity);
+    }
+}
+
+pub fn define_private_method<I: Object, O: Object>(klass: Value,
+                                                   name: &str,
+                                                   callback: Callback<I, O>) {
+    let name = util::str_to_cstring(name);
+
+    unsafe {
+        rb_define_private_method(klass, name.as_ptr(), callback as CallbackPtr, -1);
+    }
+}
+
+pub fn define_protected_method<I: Object, O: Object>(klass: Value,
+                                                     name: &str,
+                                                     callback: Callback<I, O>) {
+    let name = util::str_to_cstring(name);
+
+    unsafe {
+        rb_define_protected_method(klass, name.as_ptr(), callback as CallbackPtr, -1);
+    }
+}
+
+pub fn define_module_function<I: Object, O: Object>(module: Value,
+                                                    name: &str,
+                                                    callback: Callback<I, O>) {
+    let name = util::str_to_cstring(name);
+
+    unsafe {
+        rb_define_module_function(module, name.as_ptr(), callback as CallbackPtr, -1);
+    }
+}
+
+pub fn define_alias(klass: Value, new_name: &str, old_name: &str) {
+    let new_name = util::str_to_cstring(new_name);
+    let old_name = util::str_to_cstring(old_name);
+
+    unsafe {
+        rb_define_alias(klass, new_name.as_ptr(), old_name.as_ptr());
+    }
+}
+
+pub fn remove_method(klass: Value, name: &str) {
+    let name = util::str_to_cstring(name);
+
+    unsafe {
+        rb_remove_method(klass, name.as_ptr());
+    }
+}
+
+pub fn undef_method(klass: Value, name: &str) {
+    let name = util::str_to_cstring(name);
+
+    unsafe {
+        rb_undef_method(klass, name.as_ptr());
+    }
+}
@@ -0,0 +1,29 @@
+use types::{Argc, CallbackPtr, Value};
+
+extern "C" {
+    fn rb_fiber_alive_p(fiber: Value) -> Value;
+    fn rb_fiber_current() -> Value;
+    fn rb_fiber_new(func: CallbackPtr, callback_arg: Value) -> Value;
+    fn rb_fiber_resume(fiber: Value, argc: Argc, argv: *const Value) -> Value;
+    fn rb_fiber_yield(argc: Argc, argv: *const Value) -> Value;
+}
+
+pub fn new(func: CallbackPtr, callback_arg: Value) -> Value {
+    unsafe { rb_fiber_new(func, callback_arg) }
+}
+
+pub fn current() -> Value {
+    unsafe { rb_fiber_current() }
+}
+
+pub fn resume(fiber: Value, argc: Argc, argv: *const Value) -> Value {
+    unsafe { rb_fiber_resume(fiber, argc, argv) }
+}
+
+pub fn yield_values(argc: Argc, argv: *const Value) -> Value {
+    unsafe { rb_fiber_yield(argc, argv) }
+}
+
+pub fn is_alive(fiber: Value) -> bool {
+    unsafe { rb_fiber_alive_p(fiber).is_true() }
+}
@@ -1,11 +1,57 @@
 use ruby_sys::fixnum;
 
+#[cfg(feature = "serde")]
+use binding::vm;
 use types::{SignedValue, Value};
 
+#[cfg(feature = "serde")]
+extern "C" {
+    fn rb_big2ll(num: Value) -> i64;
+    fn rb_big2ull(num: Value) -> u64;
+    fn rb_num2ll(num: Value) -> i64;
+    fn rb_ull2inum(num: u64) -> Value;
+}
+
 pub fn int_to_num(num: i64) -> Value {
     unsafe { fixnum::rb_int2inum(num as SignedValue) }
 }
 
+#[cfg(feature = "serde")]
+pub fn uint_to_num(num: u64) -> Value {
+    unsafe { rb_ull2inum(num) }
+}
+
 pub fn num_to_int(num: Value) -> i64 {
     unsafe { fixnum::rb_num2int(num) as i64 }
 }
+
+#[cfg(feature = "serde")]
+pub fn fixnum_to_i64(num: Value) -> i64 {
+    unsafe { rb_num2ll(num) }
+}
+
+#[cfg(feature = "serde")]
+pub fn bignum_to_i64(num: Value) -> Option<i64> {
+    let mut result = 0;
+
+    match vm::protect(|| result = unsafe { rb_big2ll(num) }) {
+        Ok(_) => Some(result),
+        Err(state) => {
+            vm::take_exception(state);
+            None
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub fn bignum_to_u64(num: Value) -> Option<u64> {
+    let mut result = 0;
+
+    match vm::protect(|| result = unsafe { rb_big2ull(num) }) {
+        Ok(_) => Some(result),
+        Err(state) => {
+            vm::take_exception(state);
+            None
+        }
+    }
+}
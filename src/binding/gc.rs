@@ -0,0 +1,43 @@
+use types::Value;
+
+extern "C" {
+    fn rb_gc_disable() -> Value;
+    fn rb_gc_enable() -> Value;
+    fn rb_gc_mark_locations(start: *const Value, end: *const Value);
+    fn rb_gc_register_address(address: *mut Value);
+    fn rb_gc_register_mark_object(object: Value);
+    fn rb_gc_start() -> Value;
+    fn rb_gc_unregister_address(address: *mut Value);
+}
+
+pub fn start() {
+    unsafe {
+        rb_gc_start();
+    }
+}
+
+// Both functions return `true` if GC was disabled before the call
+pub fn disable() -> bool {
+    unsafe { rb_gc_disable().is_true() }
+}
+
+pub fn enable() -> bool {
+    unsafe { rb_gc_enable().is_true() }
+}
+
+pub fn register_address(address: *mut Value) {
+    unsafe { rb_gc_register_address(address) }
+}
+
+pub fn unregister_address(address: *mut Value) {
+    unsafe { rb_gc_unregister_address(address) }
+}
+
+pub fn register_mark_object(object: Value) {
+    unsafe { rb_gc_register_mark_object(object) }
+}
+
+// Marks every word in `start..end` which looks like a Ruby object (conservative marking)
+pub fn mark_locations(start: *const Value, end: *const Value) {
+    unsafe { rb_gc_mark_locations(start, end) }
+}
@@ -1,2 +1,45 @@
 pub use ruby_sys::rb_cObject;
 pub use ruby_sys::value::{RubySpecialConsts, ValueType};
+
+use types::{c_char, Id, Value};
+use util;
+
+pub type VariableGetter = unsafe extern "C" fn(id: Id, data: *mut Value) -> Value;
+pub type VariableSetter = unsafe extern "C" fn(value: Value, id: Id, data: *mut Value);
+
+extern "C" {
+    fn rb_define_hooked_variable(name: *const c_char,
+                                 variable: *mut Value,
+                                 getter: VariableGetter,
+                                 setter: VariableSetter);
+    fn rb_gv_get(name: *const c_char) -> Value;
+    fn rb_gv_set(name: *const c_char, value: Value) -> Value;
+    fn rb_gvar_readonly_setter(value: Value, id: Id, data: *mut Value);
+}
+
+pub fn global_get(name: &str) -> Value {
+    let name = util::str_to_cstring(name);
+
+    unsafe { rb_gv_get(name.as_ptr()) }
+}
+
+pub fn global_set(name: &str, value: Value) -> Value {
+    let name = util::str_to_cstring(name);
+
+    unsafe { rb_gv_set(name.as_ptr(), value) }
+}
+
+// `data` is stored in a leaked slot which is passed back to `getter` and `setter`. MRI marks
+// the slot as long as the variable exists, so `data` is not garbage collected.
+//
+// Variables without `setter` are read-only and raise `NameError` on assignment.
+pub fn define_hooked_variable(name: &str,
+                              data: Value,
+                              getter: VariableGetter,
+                              setter: Option<VariableSetter>) {
+    let name = util::str_to_cstring(name);
+    let variable = Box::into_raw(Box::new(data));
+    let setter = setter.unwrap_or(rb_gvar_readonly_setter as VariableSetter);
+
+    unsafe { rb_define_hooked_variable(name.as_ptr(), variable, getter, setter) }
+}
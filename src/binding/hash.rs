@@ -8,6 +8,10 @@ pub fn new() -> Value {
     unsafe { hash::rb_hash_new() }
 }
 
+pub fn dup(hash: Value) -> Value {
+    unsafe { hash::rb_hash_dup(hash) }
+}
+
 pub fn aref(hash: Value, key: Value) -> Value {
     unsafe { hash::rb_hash_aref(hash, key) }
 }
@@ -0,0 +1,14 @@
+use types::{Argc, Value};
+
+extern "C" {
+    fn rb_method_call(argc: Argc, argv: *const Value, method: Value) -> Value;
+    fn rb_obj_method(object: Value, name: Value) -> Value;
+}
+
+pub fn object_method(object: Value, name: Value) -> Value {
+    unsafe { rb_obj_method(object, name) }
+}
+
+pub fn call(method: Value, argc: Argc, argv: *const Value) -> Value {
+    unsafe { rb_method_call(argc, argv, method) }
+}
@@ -1,10 +1,17 @@
 pub mod array;
 pub mod class;
+pub mod fiber;
 pub mod fixnum;
+#[cfg(feature = "serde")]
+pub mod float;
+pub mod gc;
 pub mod global;
 pub mod hash;
+pub mod method;
 pub mod rproc;
 pub mod string;
 pub mod symbol;
+pub mod thread;
+pub mod typed_data;
 pub mod util;
 pub mod vm;
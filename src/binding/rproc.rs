@@ -1,13 +1,42 @@
 use ruby_sys::rproc;
 
 use binding::global::RubySpecialConsts;
-use types::{Argc, InternalValue, Value};
+use types::{Argc, CallbackPtr, c_int, InternalValue, Value};
+
+extern "C" {
+    fn rb_func_lambda_new(func: CallbackPtr,
+                          callback_arg: Value,
+                          min_argc: c_int,
+                          max_argc: c_int)
+                          -> Value;
+    fn rb_proc_arity(rproc: Value) -> c_int;
+    fn rb_proc_lambda_p(rproc: Value) -> Value;
+    fn rb_proc_new(func: CallbackPtr, callback_arg: Value) -> Value;
+}
+
+pub fn new(func: CallbackPtr, callback_arg: Value) -> Value {
+    unsafe { rb_proc_new(func, callback_arg) }
+}
+
+pub fn new_lambda(func: CallbackPtr, callback_arg: Value) -> Value {
+    unsafe { rb_func_lambda_new(func, callback_arg, 0, -1) }
+}
+
+pub fn arity(rproc: Value) -> c_int {
+    unsafe { rb_proc_arity(rproc) }
+}
+
+pub fn is_lambda(rproc: Value) -> bool {
+    unsafe { rb_proc_lambda_p(rproc).is_true() }
+}
 
 pub fn call(rproc: Value, argc: Argc, argv: *const Value) -> Value {
-    unsafe {
-        rproc::rb_proc_call_with_block(rproc,
-                                       argc,
-                                       argv,
-                                       Value::from(RubySpecialConsts::Nil as InternalValue))
-    }
+    call_with_block(rproc,
+                    argc,
+                    argv,
+                    Value::from(RubySpecialConsts::Nil as InternalValue))
+}
+
+pub fn call_with_block(rproc: Value, argc: Argc, argv: *const Value, block: Value) -> Value {
+    unsafe { rproc::rb_proc_call_with_block(rproc, argc, argv, block) }
 }
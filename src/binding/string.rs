@@ -3,6 +3,8 @@ use std::slice;
 use ruby_sys::string;
 
 use types::Value;
+#[cfg(feature = "serde")]
+use types::{c_char, c_long};
 use util;
 
 pub fn new(string: &str) -> Value {
@@ -11,6 +13,14 @@ pub fn new(string: &str) -> Value {
     unsafe { string::rb_str_new_cstr(str.as_ptr()) }
 }
 
+#[cfg(feature = "serde")]
+pub fn new_from_bytes(bytes: &[u8]) -> Value {
+    let ptr = bytes.as_ptr() as *const c_char;
+    let len = bytes.len() as c_long;
+
+    unsafe { string::rb_str_new(ptr, len) }
+}
+
 pub fn from_value(value: Value) -> String {
     let str = unsafe { string::rb_string_value_cstr(&value) };
 
@@ -18,13 +28,15 @@ pub fn from_value(value: Value) -> String {
 }
 
 pub fn from_value_unchecked(value: Value) -> String {
+    unsafe { String::from_utf8_unchecked(to_bytes(value)) }
+}
+
+pub fn to_bytes(value: Value) -> Vec<u8> {
     unsafe {
         let str = string::rb_string_value_ptr(&value) as *const u8;
         let len = string::rb_str_len(value) as usize;
 
-        let vec = slice::from_raw_parts(str, len).to_vec();
-
-        String::from_utf8_unchecked(vec)
+        slice::from_raw_parts(str, len).to_vec()
     }
 }
 
@@ -0,0 +1,46 @@
+use ruby_sys::thread;
+
+use binding::util as binding_util;
+use types::{c_void, Id, Value};
+
+extern "C" {
+    fn rb_thread_check_ints();
+    fn rb_thread_current() -> Value;
+    fn rb_thread_local_aref(thread: Value, id: Id) -> Value;
+    fn rb_thread_local_aset(thread: Value, id: Id, value: Value) -> Value;
+    fn rb_thread_schedule();
+}
+
+pub fn create<F>(func: F) -> Value
+    where F: FnOnce() -> Value + 'static
+{
+    let func: Box<Box<dyn FnOnce() -> Value>> = Box::new(Box::new(func));
+
+    unsafe { thread::rb_thread_create(thread_main, Box::into_raw(func) as *mut c_void) }
+}
+
+extern "C" fn thread_main(func: *mut c_void) -> Value {
+    let func = unsafe { Box::from_raw(func as *mut Box<dyn FnOnce() -> Value>) };
+
+    func()
+}
+
+pub fn current() -> Value {
+    unsafe { rb_thread_current() }
+}
+
+pub fn local_get(thread: Value, name: &str) -> Value {
+    unsafe { rb_thread_local_aref(thread, binding_util::internal_id(name)) }
+}
+
+pub fn local_set(thread: Value, name: &str, value: Value) -> Value {
+    unsafe { rb_thread_local_aset(thread, binding_util::internal_id(name), value) }
+}
+
+pub fn check_ints() {
+    unsafe { rb_thread_check_ints() }
+}
+
+pub fn schedule() {
+    unsafe { rb_thread_schedule() }
+}
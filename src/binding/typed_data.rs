@@ -0,0 +1,84 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::mem;
+use std::ptr;
+
+use ruby_sys::typed_data::{self, RbDataType, RbDataTypeFunction};
+
+use binding::gc;
+use types::{c_char, c_void, Value};
+
+struct DataType(RbDataType);
+
+unsafe impl Sync for DataType {}
+
+static BOXED_DATA_TYPE: DataType = DataType(RbDataType {
+    wrap_struct_name: b"ruru_boxed_data\0" as *const [u8; 16] as *const c_char,
+    function: RbDataTypeFunction {
+        dmark: Some(mark_boxed),
+        dfree: Some(free_boxed),
+        dsize: None,
+        reserved: [ptr::null_mut(); 2],
+    },
+    parent: ptr::null(),
+    data: ptr::null_mut(),
+    flags: Value { value: 0 },
+});
+
+// Data is kept in a `RefCell` to detect callbacks which are re-entered while the data is
+// borrowed.
+//
+// Rust types do not describe which of their fields are Ruby objects, so the memory of
+// the data is marked conservatively (like the machine stack). Objects behind other pointers
+// (for example, in a `Vec` or a `Box`) are not found.
+struct BoxedData {
+    data: Box<dyn Any>,
+    start: *const Value,
+    end: *const Value,
+}
+
+extern "C" fn mark_boxed(data: *mut c_void) {
+    let data = unsafe { &*(data as *const BoxedData) };
+
+    if data.start < data.end {
+        gc::mark_locations(data.start, data.end);
+    }
+}
+
+extern "C" fn free_boxed(data: *mut c_void) {
+    unsafe {
+        drop(Box::from_raw(data as *mut BoxedData));
+    }
+}
+
+pub fn wrap<T: Any>(klass: Value, data: T) -> Value {
+    let data = Box::new(RefCell::new(data));
+
+    // Only whole words which are aligned as `Value`s can hold Ruby objects
+    let address = &*data as *const RefCell<T> as usize;
+    let word = mem::size_of::<Value>();
+    let start = (address + word - 1) & !(word - 1);
+    let end = (address + mem::size_of::<RefCell<T>>()) & !(word - 1);
+
+    let data = Box::new(BoxedData {
+        data: data,
+        start: start as *const Value,
+        end: end as *const Value,
+    });
+
+    unsafe {
+        typed_data::rb_data_typed_object_wrap(klass,
+                                              Box::into_raw(data) as *mut c_void,
+                                              &BOXED_DATA_TYPE.0)
+    }
+}
+
+// The data lives as long as the object, so the reference must not outlive the borrowed value
+// which keeps the object alive.
+pub fn get<T: Any>(object: &Value) -> Option<&RefCell<T>> {
+    unsafe {
+        let data = typed_data::rb_check_typeddata(*object, &BOXED_DATA_TYPE.0) as *const BoxedData;
+
+        (*data).data.downcast_ref::<RefCell<T>>()
+    }
+}
@@ -1,8 +1,23 @@
 use ruby_sys::util as ruby_sys_util;
 
-use types::{Argc, Id, Value};
+use types::{Argc, c_int, Id, Value};
 use util;
 
+extern "C" {
+    fn rb_funcall_with_block(receiver: Value,
+                             method: Id,
+                             argc: Argc,
+                             argv: *const Value,
+                             block: Value)
+                             -> Value;
+    fn rb_funcallv_kw(receiver: Value,
+                      method: Id,
+                      argc: Argc,
+                      argv: *const Value,
+                      kw_splat: c_int)
+                      -> Value;
+}
+
 pub fn get_constant(name: &str, parent_object: Value) -> Value {
     let constant_id = internal_id(name);
 
@@ -20,3 +35,24 @@ pub fn call_method(receiver: Value, method: &str, argc: Argc, argv: *const Value
 
     unsafe { ruby_sys_util::rb_funcallv(receiver, method_id, argc, argv) }
 }
+
+pub fn call_method_with_kwargs(receiver: Value,
+                               method: &str,
+                               argc: Argc,
+                               argv: *const Value)
+                               -> Value {
+    let method_id = internal_id(method);
+
+    unsafe { rb_funcallv_kw(receiver, method_id, argc, argv, 1) }
+}
+
+pub fn call_method_with_block(receiver: Value,
+                              method: &str,
+                              argc: Argc,
+                              argv: *const Value,
+                              block: Value)
+                              -> Value {
+    let method_id = internal_id(method);
+
+    unsafe { rb_funcall_with_block(receiver, method_id, argc, argv, block) }
+}
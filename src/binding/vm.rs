@@ -1,23 +1,166 @@
-use ruby_sys::vm;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use types::{CallbackPtr, c_int, c_void, Value};
+use ruby_sys::{thread, vm};
+
+use binding::global::RubySpecialConsts;
+use binding::symbol;
+use binding::util as binding_util;
+use types::{Argc, CallbackPtr, c_char, c_int, c_void, Id, InternalValue, Value};
 use util;
 
+extern "C" {
+    fn rb_enumeratorize_with_size(object: Value,
+                                  method: Value,
+                                  argc: Argc,
+                                  argv: *const Value,
+                                  size_fn: CallbackPtr)
+                                  -> Value;
+    fn rb_errinfo() -> Value;
+    fn rb_eval_string_protect(code: *const c_char, state: *mut c_int) -> Value;
+    fn rb_exc_raise(exception: Value) -> !;
+    fn rb_frame_this_func() -> Id;
+    fn rb_jump_tag(state: c_int) -> !;
+    fn rb_keyword_given_p() -> c_int;
+    fn rb_load_protect(path: Value, wrap: c_int, state: *mut c_int);
+    fn rb_set_errinfo(error: Value);
+    fn rb_yield(value: Value) -> Value;
+    fn rb_yield_values2(argc: Argc, argv: *const Value) -> Value;
+    fn ruby_cleanup(status: c_int) -> c_int;
+    fn ruby_executable_node(node: *mut c_void, status: *mut c_int) -> c_int;
+    fn ruby_options(argc: c_int, argv: *mut *mut c_char) -> *mut c_void;
+    fn ruby_script(name: *const c_char);
+    fn rb_get_kwargs(keyword_hash: Value,
+                     table: *const Id,
+                     required: c_int,
+                     optional: c_int,
+                     values: *mut Value)
+                     -> c_int;
+}
+
 pub fn block_proc() -> Value {
     unsafe { vm::rb_block_proc() }
 }
 
-pub fn init() {
+pub fn is_block_given() -> bool {
+    let result = unsafe { vm::rb_block_given_p() };
+
+    util::c_int_to_bool(result)
+}
+
+pub fn is_keyword_given() -> bool {
+    let result = unsafe { rb_keyword_given_p() };
+
+    util::c_int_to_bool(result)
+}
+
+pub fn yield_value(value: Value) -> Value {
+    unsafe { rb_yield(value) }
+}
+
+pub fn yield_values(argc: Argc, argv: *const Value) -> Value {
+    unsafe { rb_yield_values2(argc, argv) }
+}
+
+pub fn enumerator_for_current_method(object: Value, argc: Argc, argv: *const Value) -> Value {
+    let method = symbol::id_to_sym(unsafe { rb_frame_this_func() });
+
+    enumeratorize(object, method, argc, argv, 0 as CallbackPtr)
+}
+
+pub fn enumeratorize(object: Value,
+                     method: Value,
+                     argc: Argc,
+                     argv: *const Value,
+                     size_fn: CallbackPtr)
+                     -> Value {
+    unsafe { rb_enumeratorize_with_size(object, method, argc, argv, size_fn) }
+}
+
+pub fn get_keywords(keyword_hash: Value, required: &[&str], optional: &[&str]) -> Vec<Value> {
+    let table = required.iter()
+        .chain(optional.iter())
+        .map(|name| binding_util::internal_id(name))
+        .collect::<Vec<Id>>();
+
+    let mut values = vec![Value::from(RubySpecialConsts::Undef as InternalValue); table.len()];
+
+    unsafe {
+        rb_get_kwargs(keyword_hash,
+                      table.as_ptr(),
+                      required.len() as c_int,
+                      optional.len() as c_int,
+                      values.as_mut_ptr());
+    }
+
+    values
+}
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+// Returns `false` without initializing the VM again if it has already been initialized
+pub fn init() -> bool {
+    if INITIALIZED.swap(true, Ordering::SeqCst) {
+        return false;
+    }
+
     unsafe {
         vm::ruby_init();
     }
+
+    true
+}
+
+// MRI keeps pointers to `argv` for the whole life of the process, so arguments are leaked.
+pub fn process_options(arguments: &[String]) -> Result<(), c_int> {
+    let mut argv = arguments.iter()
+        .map(|argument| util::str_to_cstring(argument).into_raw())
+        .collect::<Vec<*mut c_char>>();
+
+    argv.push(ptr::null_mut());
+
+    let argc = arguments.len() as c_int;
+    let argv = Box::leak(argv.into_boxed_slice()).as_mut_ptr();
+    let mut status = 0;
+
+    unsafe {
+        let node = ruby_options(argc, argv);
+
+        if ruby_executable_node(node, &mut status) == 0 {
+            return Err(status);
+        }
+    }
+
+    Ok(())
 }
 
-pub fn require(name: &str) {
+pub fn set_script_name(name: &str) {
     let name = util::str_to_cstring(name);
 
+    unsafe { ruby_script(name.as_ptr()) }
+}
+
+pub fn cleanup() -> c_int {
+    unsafe { ruby_cleanup(0) }
+}
+
+pub fn require(name: &str) -> Value {
+    let name = util::str_to_cstring(name);
+
+    unsafe { vm::rb_require(name.as_ptr()) }
+}
+
+pub fn load(path: Value, wrap: bool) -> Result<(), Value> {
+    let mut state = 0;
+
     unsafe {
-        vm::rb_require(name.as_ptr());
+        rb_load_protect(path, util::bool_to_c_int(wrap), &mut state as *mut c_int);
+    }
+
+    if state == 0 {
+        Ok(())
+    } else {
+        Err(take_exception(state))
     }
 }
 
@@ -29,18 +172,68 @@ pub fn raise(exception: Value, message: &str) {
     }
 }
 
+pub fn errinfo() -> Value {
+    unsafe { rb_errinfo() }
+}
+
+pub fn clear_errinfo() {
+    unsafe { rb_set_errinfo(Value::from(RubySpecialConsts::Nil as InternalValue)) }
+}
+
+pub fn jump_tag(state: c_int) -> ! {
+    unsafe { rb_jump_tag(state) }
+}
+
+pub fn raise_exception(exception: Value) -> ! {
+    unsafe { rb_exc_raise(exception) }
+}
+
+// Returns the exception which interrupted a protected call with `state` and clears `$!`.
+//
+// Jumps which are not caused by exceptions are resumed.
+pub fn take_exception(state: c_int) -> Value {
+    let exception = errinfo();
+
+    if exception.is_nil() {
+        jump_tag(state);
+    }
+
+    clear_errinfo();
+
+    exception
+}
+
+pub fn rescue<F>(func: F) -> Result<Value, Value>
+    where F: FnOnce() -> Value
+{
+    protect_value(func).map_err(take_exception)
+}
+
+pub fn eval_string(code: &str) -> Result<Value, Value> {
+    let code = util::str_to_cstring(code);
+    let mut state = 0;
+
+    let value = unsafe { rb_eval_string_protect(code.as_ptr(), &mut state as *mut c_int) };
+
+    if state == 0 {
+        Ok(value)
+    } else {
+        Err(take_exception(state))
+    }
+}
+
 pub fn thread_call_without_gvl<F, R, G>(func: F, unblock_func: Option<G>) -> R
     where F: FnOnce() -> R,
           G: FnOnce()
 {
     unsafe {
         let ptr = if let Some(ubf) = unblock_func {
-            vm::rb_thread_call_without_gvl(callbox as CallbackPtr,
+            thread::rb_thread_call_without_gvl(callbox as CallbackPtr,
                                            util::closure_to_ptr(func),
                                            callbox as CallbackPtr,
                                            util::closure_to_ptr(ubf))
         } else {
-            vm::rb_thread_call_without_gvl(callbox as CallbackPtr,
+            thread::rb_thread_call_without_gvl(callbox as CallbackPtr,
                                            util::closure_to_ptr(func),
                                            0 as CallbackPtr,
                                            0 as *const c_void)
@@ -56,12 +249,12 @@ pub fn thread_call_without_gvl2<F, R, G>(func: F, unblock_func: Option<G>) -> R
 {
     unsafe {
         let ptr = if let Some(ubf) = unblock_func {
-            vm::rb_thread_call_without_gvl2(callbox as CallbackPtr,
+            thread::rb_thread_call_without_gvl2(callbox as CallbackPtr,
                                             util::closure_to_ptr(func),
                                             callbox as CallbackPtr,
                                             util::closure_to_ptr(ubf))
         } else {
-            vm::rb_thread_call_without_gvl2(callbox as CallbackPtr,
+            thread::rb_thread_call_without_gvl2(callbox as CallbackPtr,
                                             util::closure_to_ptr(func),
                                             0 as CallbackPtr,
                                             0 as *const c_void)
@@ -71,11 +264,30 @@ pub fn thread_call_without_gvl2<F, R, G>(func: F, unblock_func: Option<G>) -> R
     }
 }
 
+// Unlike `thread_call_without_gvl`, `unblock_func` is not consumed, because MRI may call it
+// several times
+pub fn thread_call_without_gvl_with_ubf<F, R>(func: F,
+                                              unblock_func: CallbackPtr,
+                                              unblock_args: *const c_void)
+                                              -> R
+    where F: FnOnce() -> R
+{
+    unsafe {
+        let ptr = thread::rb_thread_call_without_gvl(callbox as CallbackPtr,
+                                                     util::closure_to_ptr(func),
+                                                     unblock_func,
+                                                     unblock_args);
+
+        util::ptr_to_data(ptr)
+    }
+}
+
 pub fn thread_call_with_gvl<F, R>(func: F) -> R
     where F: FnOnce() -> R
 {
     unsafe {
-        let ptr = vm::rb_thread_call_with_gvl(callbox as CallbackPtr, util::closure_to_ptr(func));
+        let ptr = thread::rb_thread_call_with_gvl(callbox as CallbackPtr,
+                                                  util::closure_to_ptr(func));
 
         util::ptr_to_data(ptr)
     }
@@ -105,3 +317,20 @@ pub fn protect<F>(func: F) -> Result<Value, c_int>
         Err(state)
     }
 }
+
+pub fn protect_value<F>(func: F) -> Result<Value, c_int>
+    where F: FnOnce() -> Value
+{
+    let mut state = 0;
+    let ptr = unsafe {
+        vm::rb_protect(callbox as CallbackPtr,
+                       util::closure_to_ptr(func),
+                       &mut state as *mut c_int)
+    };
+
+    if state == 0 {
+        Ok(unsafe { util::ptr_to_data(ptr.value as *mut c_void) })
+    } else {
+        Err(state)
+    }
+}
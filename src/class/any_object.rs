@@ -1,3 +1,5 @@
+use std::marker::PhantomData;
+
 use types::Value;
 
 use {Object, VerifiedObject};
@@ -49,13 +51,15 @@ use {Object, VerifiedObject};
 ///
 /// You can find more examples in `Class`, `Object` and `VerifiedObject` documentation.
 #[derive(Clone, Debug, PartialEq)]
+#[repr(transparent)]
 pub struct AnyObject {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl From<Value> for AnyObject {
     fn from(value: Value) -> Self {
-        AnyObject { value: value }
+        AnyObject { value: value, _not_send: PhantomData }
     }
 }
 
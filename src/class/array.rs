@@ -1,5 +1,6 @@
 use std::convert::From;
 use std::iter::{FromIterator, IntoIterator, Iterator};
+use std::marker::PhantomData;
 
 use binding::array;
 use types::{Value, ValueType};
@@ -8,8 +9,10 @@ use {AnyObject, RString, Object, VerifiedObject};
 
 /// `Array`
 #[derive(Debug, PartialEq)]
+#[repr(transparent)]
 pub struct Array {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl Array {
@@ -181,7 +184,7 @@ impl Array {
 
 impl From<Value> for Array {
     fn from(value: Value) -> Self {
-        Array { value: value }
+        Array { value: value, _not_send: PhantomData }
     }
 }
 
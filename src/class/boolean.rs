@@ -1,4 +1,5 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use types::{Value, ValueType};
 use util;
@@ -7,8 +8,10 @@ use {Object, VerifiedObject};
 
 /// `TrueClass` and `FalseClass`
 #[derive(Debug, PartialEq)]
+#[repr(transparent)]
 pub struct Boolean {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl Boolean {
@@ -55,7 +58,7 @@ impl Boolean {
 
 impl From<Value> for Boolean {
     fn from(value: Value) -> Self {
-        Boolean { value: value }
+        Boolean { value: value, _not_send: PhantomData }
     }
 }
 
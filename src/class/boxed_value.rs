@@ -0,0 +1,79 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Deref;
+
+use binding::gc;
+use types::Value;
+
+use Object;
+
+/// A Ruby object which is kept alive while it is held by Rust
+///
+/// Ruby GC only knows about objects which are referenced from Ruby or from the stack.
+/// Objects stored in Rust structures (caches, statics, other heap-allocated data) may be
+/// garbage collected. `BoxedValue` registers the object as a GC root when it is created and
+/// unregisters it when it is dropped.
+///
+/// `BoxedValue<T>` dereferences to `T`, so it can be used as the object itself.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use ruru::{BoxedValue, Object, RString, VM};
+/// # VM::init();
+///
+/// let mut cache = HashMap::new();
+///
+/// cache.insert("greeting", BoxedValue::new(RString::new("Hello")));
+///
+/// // The string is not collected while it is in the cache
+/// VM::gc_start();
+///
+/// assert_eq!(cache["greeting"].to_string(), "Hello".to_string());
+/// ```
+pub struct BoxedValue<T: Object> {
+    // Registered address, it must not move while the value is registered
+    value: Box<Value>,
+    object: T,
+}
+
+impl<T: Object> BoxedValue<T> {
+    /// Wraps an object and registers it as a GC root.
+    pub fn new(object: T) -> Self {
+        let mut value = Box::new(object.value());
+
+        gc::register_address(&mut *value);
+
+        BoxedValue {
+            value: value,
+            object: object,
+        }
+    }
+}
+
+impl<T: Object> Deref for BoxedValue<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.object
+    }
+}
+
+impl<T: Object + From<Value>> Clone for BoxedValue<T> {
+    fn clone(&self) -> Self {
+        Self::new(T::from(*self.value))
+    }
+}
+
+impl<T: Object + Debug> Debug for BoxedValue<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("BoxedValue").field(&self.object).finish()
+    }
+}
+
+impl<T: Object> Drop for BoxedValue<T> {
+    fn drop(&mut self) {
+        gc::unregister_address(&mut *self.value);
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use types::c_void;
+
+/// A flag which tells native code running without the GVL to stop
+///
+/// `VM::thread_call_without_gvl_cancellable()` passes a token to its closure and cancels it
+/// when Ruby interrupts the thread (`Thread#kill`, `Thread#raise`, `Timeout.timeout`, signals
+/// etc). Long-running computations should check `is_cancelled()` regularly and return early.
+///
+/// Tokens can be cloned and shared with other Rust threads.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new token which is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token and all its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Checks if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+pub extern "C" fn cancel_token(token: *const c_void) {
+    let token = unsafe { &*(token as *const CancellationToken) };
+
+    token.cancel();
+}
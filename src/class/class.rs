@@ -1,12 +1,16 @@
+use std::any::Any;
 use std::convert::From;
+use std::marker::PhantomData;
+use std::ptr;
 
-use binding::class;
+use binding::{class, typed_data, vm};
 use binding::global::rb_cObject;
 use binding::util as binding_util;
 use types::{Value, ValueType};
 use util;
 
-use {AnyObject, Array, Object, VerifiedObject};
+use {AnyObject, Array, Boolean, Exception, Fixnum, NilClass, Object, Proc, RString, Symbol,
+     UnboundMethod, VerifiedObject};
 
 /// `Class`
 ///
@@ -56,8 +60,10 @@ use {AnyObject, Array, Object, VerifiedObject};
 /// end
 /// ```
 #[derive(Debug, PartialEq)]
+#[repr(transparent)]
 pub struct Class {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl Class {
@@ -171,6 +177,35 @@ impl Class {
         AnyObject::from(instance)
     }
 
+    /// Creates a new instance of the class which wraps Rust `data`.
+    ///
+    /// The instance is created without calling `initialize`. The data is dropped when
+    /// the instance is garbage collected. Use `Object::get_data()` to access the data.
+    ///
+    /// The memory of the data is marked by the garbage collector conservatively, so Ruby objects
+    /// stored directly in it are kept alive. Objects behind other pointers (for example, in
+    /// a `Vec`) are not marked and must be wrapped in `BoxedValue`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// struct Counter {
+    ///     count: i64,
+    /// }
+    ///
+    /// let counter = Class::new("Counter", None).wrap_data(Counter { count: 1 });
+    ///
+    /// let count = unsafe { counter.get_data::<Counter>() }.map(|counter| counter.borrow().count);
+    ///
+    /// assert_eq!(count, Some(1));
+    /// ```
+    pub fn wrap_data<T: Any>(&self, data: T) -> AnyObject {
+        AnyObject::from(typed_data::wrap(self.value(), data))
+    }
+
     /// Returns a superclass of the current class
     ///
     /// # Examples
@@ -385,6 +420,398 @@ impl Class {
         class::define_attribute(self.value(), name, true, true);
     }
 
+    /// Returns an `UnboundMethod` for the instance method with given name.
+    ///
+    /// Raises `NameError` if the method is not defined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, VM};
+    /// # VM::init();
+    ///
+    /// let length = Class::from_existing("String").instance_method("length");
+    ///
+    /// assert_eq!(length.owner(), Class::from_existing("String"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// String.instance_method(:length).owner == String
+    /// ```
+    pub fn instance_method(&self, name: &str) -> UnboundMethod {
+        let name = Symbol::new(name).to_any_object();
+
+        UnboundMethod::from(self.send("instance_method", vec![name]).value())
+    }
+
+    /// Returns names of public and protected instance methods of the class.
+    ///
+    /// Methods of ancestors are included if `include_inherited` is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// let methods = Class::from_existing("String").instance_methods(false);
+    ///
+    /// assert!(methods.contains(&Symbol::new("length")));
+    /// assert!(!methods.contains(&Symbol::new("object_id")));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// methods = String.instance_methods(false)
+    ///
+    /// methods.include?(:length) == true
+    /// methods.include?(:object_id) == false
+    /// ```
+    // Using unsafe conversions is ok, because MRI guarantees to return an `Array` of `Symbol`s
+    pub fn instance_methods(&self, include_inherited: bool) -> Vec<Symbol> {
+        let include_inherited = Boolean::new(include_inherited).to_any_object();
+        let methods = self.send("instance_methods", vec![include_inherited]);
+
+        unsafe { methods.to::<Array>() }
+            .into_iter()
+            .map(|name| unsafe { name.to::<Symbol>() })
+            .collect()
+    }
+
+    /// Checks whether a public or protected instance method is defined in the class or
+    /// its ancestors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, VM};
+    /// # VM::init();
+    ///
+    /// let string_class = Class::from_existing("String");
+    ///
+    /// assert!(string_class.method_defined("length"));
+    /// assert!(!string_class.method_defined("something_else"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// String.method_defined?(:length) == true
+    /// String.method_defined?(:something_else) == false
+    /// ```
+    pub fn method_defined(&self, name: &str) -> bool {
+        let name = Symbol::new(name).to_any_object();
+
+        self.send("method_defined?", vec![name]).value().is_true()
+    }
+
+    /// Removes the method defined in the class.
+    ///
+    /// Methods with the same name defined in ancestors are still available.
+    /// Raises `NameError` if the method is not defined in the class itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// let mut class = Class::new("Record", None);
+    ///
+    /// class.attr_reader("id");
+    /// assert!(class.method_defined("id"));
+    ///
+    /// class.remove_method("id");
+    /// assert!(!class.method_defined("id"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Record
+    ///   attr_reader :id
+    ///
+    ///   remove_method :id
+    /// end
+    /// ```
+    pub fn remove_method(&mut self, name: &str) {
+        class::remove_method(self.value(), name);
+    }
+
+    /// Prevents the class from responding to the method, including methods defined
+    /// in ancestors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// let mut class = Class::new("Record", None);
+    ///
+    /// class.undef_method("to_s");
+    ///
+    /// let record = class.new_instance(vec![]);
+    ///
+    /// assert!(!record.respond_to("to_s"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Record
+    ///   undef_method :to_s
+    /// end
+    ///
+    /// Record.new.respond_to?(:to_s) == false
+    /// ```
+    pub fn undef_method(&mut self, name: &str) {
+        class::undef_method(self.value(), name);
+    }
+
+    /// Defines `new_name` as an alias of the instance method `old_name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// let mut class = Class::new("Record", None);
+    ///
+    /// class.attr_reader("id");
+    /// class.alias_method("legacy_id", "id");
+    ///
+    /// assert!(class.method_defined("legacy_id"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Record
+    ///   attr_reader :id
+    ///
+    ///   alias_method :legacy_id, :id
+    /// end
+    /// ```
+    pub fn alias_method(&mut self, new_name: &str, old_name: &str) {
+        class::define_alias(self.value(), new_name, old_name);
+    }
+
+    /// Makes an existing instance method public.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// let mut class = Class::new("Record", None);
+    ///
+    /// class.make_private("to_s");
+    /// assert!(!class.new_instance(vec![]).respond_to("to_s"));
+    ///
+    /// class.make_public("to_s");
+    /// assert!(class.new_instance(vec![]).respond_to("to_s"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Record
+    ///   private :to_s
+    ///   public :to_s
+    /// end
+    /// ```
+    pub fn make_public(&mut self, name: &str) {
+        self.set_visibility("public", name);
+    }
+
+    /// Makes an existing instance method private.
+    ///
+    /// See `make_public()` for examples.
+    pub fn make_private(&mut self, name: &str) {
+        self.set_visibility("private", name);
+    }
+
+    /// Makes an existing instance method protected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// let mut class = Class::new("Record", None);
+    ///
+    /// class.attr_reader("id");
+    /// class.make_protected("id");
+    ///
+    /// assert!(!class.new_instance(vec![]).respond_to("id"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Record
+    ///   attr_reader :id
+    ///   protected :id
+    /// end
+    /// ```
+    pub fn make_protected(&mut self, name: &str) {
+        self.set_visibility("protected", name);
+    }
+
+    /// Evaluates a string of Ruby code in the context of a class, like reopening it
+    /// with `class ... end`.
+    ///
+    /// Returns the exception if the code raises one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let mut counter = Class::new("Counter", None);
+    ///
+    /// counter.class_eval("def increment(value) value + 1 end").unwrap();
+    ///
+    /// let counter = counter.new_instance(vec![]);
+    /// let result = counter.send("increment", vec![Fixnum::new(1).to_any_object()]);
+    ///
+    /// assert_eq!(result.try_convert_to::<Fixnum>(), Ok(Fixnum::new(2)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Counter
+    /// end
+    ///
+    /// Counter.class_eval('def increment(value) value + 1 end')
+    ///
+    /// Counter.new.increment(1) == 2
+    /// ```
+    pub fn class_eval(&mut self, code: &str) -> Result<AnyObject, Exception> {
+        let receiver = self.value();
+        let code = RString::new(code).value();
+
+        vm::rescue(|| binding_util::call_method(receiver, "class_eval", 1, &code))
+            .map(AnyObject::from)
+            .map_err(Exception::from)
+    }
+
+    /// Evaluates a string of Ruby code in the context of a class, reporting `file` and `line`
+    /// as its location.
+    ///
+    /// `file` and `line` are used in backtraces, `__FILE__`/`__LINE__` of the evaluated code
+    /// and source locations of the methods it defines.
+    ///
+    /// Returns the exception if the code raises one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, Class, Fixnum, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let mut counter = Class::new("Counter", None);
+    ///
+    /// counter.class_eval_at("def increment(value) value + 1 end", "counter.rb", 3).unwrap();
+    ///
+    /// let location = counter.instance_method("increment").send("source_location", vec![]);
+    /// let location = location.try_convert_to::<Array>().unwrap();
+    ///
+    /// assert_eq!(location.at(0).try_convert_to::<RString>().unwrap().to_string(),
+    ///            "counter.rb".to_string());
+    /// assert_eq!(location.at(1).try_convert_to::<Fixnum>(), Ok(Fixnum::new(3)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Counter
+    /// end
+    ///
+    /// Counter.class_eval('def increment(value) value + 1 end', 'counter.rb', 3)
+    ///
+    /// Counter.instance_method(:increment).source_location == ['counter.rb', 3]
+    /// ```
+    pub fn class_eval_at(&mut self,
+                         code: &str,
+                         file: &str,
+                         line: i64)
+                         -> Result<AnyObject, Exception> {
+        let receiver = self.value();
+        let arguments = vec![RString::new(code).to_any_object(),
+                             RString::new(file).to_any_object(),
+                             Fixnum::new(line).to_any_object()];
+        let (argc, argv) = util::create_arguments(arguments);
+
+        vm::rescue(|| binding_util::call_method(receiver, "class_eval", argc, argv.as_ptr()))
+            .map(AnyObject::from)
+            .map_err(Exception::from)
+    }
+
+    /// Calls a closure in the context of a class, like `class_eval` with a block.
+    ///
+    /// The closure receives the class as its argument. Returns the exception if the closure
+    /// raises one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// let mut record = Class::new("Record", None);
+    ///
+    /// record.class_eval_with(|itself| {
+    ///     itself.send("attr_accessor", vec![Symbol::new("id").to_any_object()])
+    /// }).unwrap();
+    ///
+    /// assert!(record.method_defined("id="));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Record
+    /// end
+    ///
+    /// Record.class_eval { |itself| itself.send(:attr_accessor, :id) }
+    /// ```
+    pub fn class_eval_with<F>(&mut self, mut func: F) -> Result<AnyObject, Exception>
+        where F: FnMut(AnyObject) -> AnyObject + 'static
+    {
+        let receiver = self.value();
+        let block = Proc::new(move |arguments: Vec<AnyObject>| {
+            let itself = arguments.into_iter()
+                .next()
+                .unwrap_or_else(|| NilClass::new().to_any_object());
+
+            func(itself)
+        });
+
+        vm::rescue(|| {
+            binding_util::call_method_with_block(receiver,
+                                                 "class_eval",
+                                                 0,
+                                                 ptr::null(),
+                                                 block.value())
+        })
+            .map(AnyObject::from)
+            .map_err(Exception::from)
+    }
+
+    fn set_visibility(&mut self, visibility: &str, name: &str) {
+        self.send(visibility, vec![Symbol::new(name).to_any_object()]);
+    }
+
     fn superclass_to_value(superclass: Option<&Class>) -> Value {
         match superclass {
             Some(class) => class.value(),
@@ -395,7 +822,7 @@ impl Class {
 
 impl From<Value> for Class {
     fn from(value: Value) -> Self {
-        Class { value: value }
+        Class { value: value, _not_send: PhantomData }
     }
 }
 
@@ -0,0 +1,267 @@
+use std::convert::From;
+use std::iter::{IntoIterator, Iterator};
+use std::marker::PhantomData;
+use std::ptr;
+
+use binding::{class, symbol, typed_data, vm};
+use binding::global::rb_cObject;
+use binding::util as binding_util;
+use types::{Argc, CallbackPtr, Value};
+
+use {AnyObject, Class, Exception, Fixnum, NilClass, Object, VerifiedObject, VM};
+
+/// `Enumerator`
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+pub struct Enumerator {
+    value: Value,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl Enumerator {
+    /// Creates a lazy `Enumerator` from a Rust iterator.
+    ///
+    /// Items are taken from the iterator only when Ruby asks for them, so the iterator is never
+    /// collected into an `Array`. If the iterator knows its exact size (as `ExactSizeIterator`s
+    /// do), it is returned by `Enumerator#size`, otherwise `size` returns `nil`.
+    ///
+    /// The iterator is consumed only once. Enumerating it again continues from the item
+    /// where the previous enumeration stopped.
+    ///
+    /// The iterator is owned by a Ruby object which marks Ruby objects stored in the iterator
+    /// itself (for example, captured by its closures). GC does not see objects behind other
+    /// pointers, so Ruby objects in collections owned by the iterator (for example, when
+    /// iterating over a `Vec<AnyObject>` or `Array::into_iter()`) must be wrapped in
+    /// `BoxedValue` to be kept alive. Use `Enumerator::from_enumerable()` to enumerate
+    /// an existing Ruby collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, Enumerator, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let enumerator = Enumerator::new((1..4).map(Fixnum::new));
+    ///
+    /// let size = enumerator.send("size", vec![]).try_convert_to::<Fixnum>();
+    /// assert_eq!(size, Ok(Fixnum::new(3)));
+    ///
+    /// let array = enumerator.send("to_a", vec![]).try_convert_to::<Array>().unwrap();
+    /// assert_eq!(array.length(), 3);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// enumerator = (1..3).each
+    ///
+    /// enumerator.size == 3
+    /// enumerator.to_a.length == 3
+    /// ```
+    ///
+    /// Enumerating Ruby objects owned by Rust:
+    ///
+    /// ```
+    /// use ruru::{Array, BoxedValue, Enumerator, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let words = vec![BoxedValue::new(RString::new("Hello")),
+    ///                  BoxedValue::new(RString::new("World"))];
+    /// let enumerator = Enumerator::new(words.into_iter().map(|word| word.to_any_object()));
+    ///
+    /// // Strings are not collected until the enumerator yields them
+    /// VM::gc_start();
+    ///
+    /// let array = enumerator.send("to_a", vec![]).try_convert_to::<Array>().unwrap();
+    /// assert_eq!(array.length(), 2);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// enumerator = ['Hello', 'World'].each
+    ///
+    /// enumerator.to_a.length == 2
+    /// ```
+    pub fn new<I, T>(iterator: I) -> Self
+        where I: Iterator<Item = T> + 'static,
+              T: Object + 'static
+    {
+        Self::from_iterator_source(iterator.map(|item| item.to_any_object()))
+    }
+
+    /// Creates an `Enumerator` over any object which responds to `each` (for example,
+    /// an `Enumerable`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, Enumerator, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let array: Array = (1..4).map(|num| Fixnum::new(num).to_any_object()).collect();
+    /// let enumerator = Enumerator::from_enumerable(&array);
+    ///
+    /// let sum: i64 = enumerator
+    ///     .into_iter()
+    ///     .map(|item| item.unwrap().try_convert_to::<Fixnum>().unwrap().to_i64())
+    ///     .sum();
+    ///
+    /// assert_eq!(sum, 6);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// enumerator = [1, 2, 3].to_enum
+    ///
+    /// enumerator.sum == 6
+    /// ```
+    pub fn from_enumerable<T: Object>(object: &T) -> Self {
+        Self::from(object.send("to_enum", vec![]).value())
+    }
+
+    fn from_iterator_source<I>(iterator: I) -> Self
+        where I: Iterator<Item = AnyObject> + 'static
+    {
+        let source = typed_data::wrap(unsafe { rb_cObject }, IteratorSource { iterator: iterator });
+
+        class::define_singleton_method(source, "each", iterator_source_each::<I>);
+
+        let method = symbol::id_to_sym(binding_util::internal_id("each"));
+        let size_fn = iterator_source_size::<I> as CallbackPtr;
+
+        Self::from(vm::enumeratorize(source, method, 0, ptr::null(), size_fn))
+    }
+}
+
+impl From<Value> for Enumerator {
+    fn from(value: Value) -> Self {
+        Enumerator { value: value, _not_send: PhantomData }
+    }
+}
+
+impl Object for Enumerator {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Enumerator {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        class::is_kind_of(object.value(), Class::from_existing("Enumerator").value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Enumerator"
+    }
+}
+
+/// Iterates over a Ruby `Enumerator` using `Enumerator#next`.
+///
+/// Iteration starts at the current position of the enumerator and stops when it
+/// raises `StopIteration`. Other exceptions are returned as `Err` items.
+pub struct EnumeratorIterator {
+    enumerator: Enumerator,
+}
+
+impl Iterator for EnumeratorIterator {
+    type Item = Result<AnyObject, Exception>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let enumerator = self.enumerator.value();
+        let result = vm::rescue(|| binding_util::call_method(enumerator, "next", 0, ptr::null()));
+
+        match result {
+            Ok(item) => Some(Ok(AnyObject::from(item))),
+            Err(exception) => {
+                let stop_iteration = Class::from_existing("StopIteration");
+
+                if class::is_kind_of(exception, stop_iteration.value()) {
+                    None
+                } else {
+                    Some(Err(Exception::from(exception)))
+                }
+            }
+        }
+    }
+}
+
+/// Allows Enumerators to be iterable in Rust.
+///
+/// # Examples
+///
+/// ```
+/// use ruru::{Enumerator, Fixnum, Object, VM};
+/// # VM::init();
+///
+/// let enumerator = Enumerator::new((1..4).map(Fixnum::new));
+/// let mut sum: i64 = 0;
+///
+/// for item in enumerator.into_iter() {
+///     sum += item.unwrap().try_convert_to::<Fixnum>().unwrap().to_i64();
+/// }
+///
+/// assert_eq!(sum, 6);
+/// ```
+///
+/// Exceptions raised by the enumerator are returned instead of being raised through Rust:
+///
+/// ```
+/// use ruru::{Class, Enumerator, Object, VM};
+/// # VM::init();
+///
+/// let enumerator = VM::eval("Enumerator.new { |y| y << 1; raise ArgumentError }").unwrap();
+/// let enumerator = enumerator.try_convert_to::<Enumerator>().unwrap();
+///
+/// let mut items = enumerator.into_iter();
+///
+/// assert!(items.next().unwrap().is_ok());
+/// assert_eq!(items.next().unwrap().unwrap_err().class(), Class::from_existing("ArgumentError"));
+/// ```
+impl IntoIterator for Enumerator {
+    type Item = Result<AnyObject, Exception>;
+    type IntoIter = EnumeratorIterator;
+
+    fn into_iter(self) -> Self::IntoIter {
+        EnumeratorIterator { enumerator: self }
+    }
+}
+
+struct IteratorSource<I> {
+    iterator: I,
+}
+
+// The iterator is released before an item is yielded, so the block can enumerate the source
+// again
+fn next_item<I>(source: &AnyObject) -> Option<AnyObject>
+    where I: Iterator<Item = AnyObject> + 'static
+{
+    VM::with_data_mut(source, |source: &mut IteratorSource<I>| source.iterator.next())
+}
+
+extern "C" fn iterator_source_each<I>(_argc: Argc,
+                                      _argv: *const AnyObject,
+                                      itself: AnyObject)
+                                      -> AnyObject
+    where I: Iterator<Item = AnyObject> + 'static
+{
+    while let Some(item) = next_item::<I>(&itself) {
+        vm::yield_value(item.value());
+    }
+
+    itself
+}
+
+extern "C" fn iterator_source_size<I>(source: Value, _arguments: Value, _enumerator: Value) -> Value
+    where I: Iterator<Item = AnyObject> + 'static
+{
+    let source = AnyObject::from(source);
+    let size_hint = VM::with_data(&source, |source: &IteratorSource<I>| source.iterator.size_hint());
+
+    match size_hint {
+        (lower, Some(upper)) if lower == upper => Fixnum::new(lower as i64).value(),
+        _ => NilClass::new().value(),
+    }
+}
@@ -0,0 +1,166 @@
+use std::convert::From;
+use std::marker::PhantomData;
+
+use binding::{class, vm};
+use types::Value;
+
+use {Array, Class, Object, RString, VerifiedObject};
+
+/// `Exception`
+///
+/// An instance of `Exception` or any of its subclasses. Functions which run Ruby code in
+/// protected mode (like `VM::eval()`) return it instead of unwinding through Rust.
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+pub struct Exception {
+    value: Value,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl Exception {
+    /// Creates a new instance of `class` with a given message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Exception, Object, VM};
+    /// # VM::init();
+    ///
+    /// let exception = Exception::new(&Class::from_existing("ArgumentError"), "Wrong argument");
+    ///
+    /// assert_eq!(exception.class(), Class::from_existing("ArgumentError"));
+    /// assert_eq!(exception.message(), "Wrong argument".to_string());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// exception = ArgumentError.new('Wrong argument')
+    ///
+    /// exception.class == ArgumentError
+    /// exception.message == 'Wrong argument'
+    /// ```
+    pub fn new(class: &Class, message: &str) -> Self {
+        let message = RString::new(message).to_any_object();
+
+        Self::from(class.new_instance(vec![message]).value())
+    }
+
+    /// Returns the message of an exception.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::VM;
+    /// # VM::init();
+    ///
+    /// let exception = VM::eval("raise 'Something went wrong'").unwrap_err();
+    ///
+    /// assert_eq!(exception.message(), "Something went wrong".to_string());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// begin
+    ///   raise 'Something went wrong'
+    /// rescue => exception
+    ///   exception.message == 'Something went wrong'
+    /// end
+    /// ```
+    pub fn message(&self) -> String {
+        let message = self.send("message", vec![]);
+
+        match message.try_convert_to::<RString>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::new(),
+        }
+    }
+
+    /// Returns the backtrace of an exception or `None` if the exception was not raised.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::VM;
+    /// # VM::init();
+    ///
+    /// let exception = VM::eval("raise 'Something went wrong'").unwrap_err();
+    ///
+    /// assert!(exception.backtrace().is_some());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// begin
+    ///   raise 'Something went wrong'
+    /// rescue => exception
+    ///   !exception.backtrace.nil?
+    /// end
+    /// ```
+    pub fn backtrace(&self) -> Option<Vec<String>> {
+        let backtrace = self.send("backtrace", vec![]);
+
+        if backtrace.is_nil() {
+            return None;
+        }
+
+        let backtrace = unsafe { backtrace.to::<Array>() };
+        let lines = (0..backtrace.length() as i64)
+            .filter_map(|index| backtrace.at(index).try_convert_to::<RString>().ok())
+            .map(|line| line.to_string())
+            .collect();
+
+        Some(lines)
+    }
+
+    /// Raises an exception.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ruru::VM;
+    /// # VM::init();
+    ///
+    /// if let Err(exception) = VM::eval("raise 'Something went wrong'") {
+    ///     exception.raise();
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// begin
+    ///   raise 'Something went wrong'
+    /// rescue => exception
+    ///   raise exception
+    /// end
+    /// ```
+    pub fn raise(&self) -> ! {
+        vm::raise_exception(self.value())
+    }
+}
+
+impl From<Value> for Exception {
+    fn from(value: Value) -> Self {
+        Exception { value: value, _not_send: PhantomData }
+    }
+}
+
+impl Object for Exception {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Exception {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        class::is_kind_of(object.value(), Class::from_existing("Exception").value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Exception"
+    }
+}
@@ -0,0 +1,74 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use binding::vm;
+use types::{CallbackPtr, c_void};
+
+use CancellationToken;
+
+// Wakes the thread which runs the executor
+struct ThreadWaker {
+    thread: Thread,
+}
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.thread.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.thread.unpark();
+    }
+}
+
+// Passed to the unblocking function which is called by Ruby from another thread
+struct Interruption {
+    token: CancellationToken,
+    thread: Thread,
+}
+
+extern "C" fn interrupt(interruption: *const c_void) {
+    let interruption = unsafe { &*(interruption as *const Interruption) };
+
+    interruption.token.cancel();
+    interruption.thread.unpark();
+}
+
+// Polls `future` on the current thread with the GVL released. The thread sleeps while
+// the future is pending and is woken up either by the future's waker or by Ruby interrupts.
+//
+// Returns `None` if the future was dropped because Ruby interrupted the thread.
+pub fn block_on<F>(future: F) -> Option<F::Output>
+    where F: Future + Send,
+          F::Output: Send
+{
+    let interruption = Interruption {
+        token: CancellationToken::new(),
+        thread: thread::current(),
+    };
+
+    let token = interruption.token.clone();
+
+    let run = move || {
+        let waker = Waker::from(Arc::new(ThreadWaker { thread: thread::current() }));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            if token.is_cancelled() {
+                return None;
+            }
+
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return Some(output),
+                Poll::Pending => thread::park(),
+            }
+        }
+    };
+
+    vm::thread_call_without_gvl_with_ubf(run,
+                                         interrupt as CallbackPtr,
+                                         &interruption as *const Interruption as *const c_void)
+}
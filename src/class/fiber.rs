@@ -0,0 +1,156 @@
+use std::convert::From;
+use std::marker::PhantomData;
+
+use binding::{class, fiber, typed_data};
+use types::{Argc, CallbackPtr, Value};
+
+use {AnyObject, Class, NilClass, Object, VerifiedObject, VM};
+
+/// `Fiber`
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+pub struct Fiber {
+    value: Value,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl Fiber {
+    /// Creates a new `Fiber` which runs a Rust closure when it is resumed for the first time.
+    ///
+    /// The closure receives the value passed to the first `resume()`. Its result is returned
+    /// by the last `resume()` and the fiber is finished after that. The closure is dropped
+    /// when it finishes, or when the fiber is garbage collected if it is never resumed.
+    ///
+    /// If a started fiber is never finished, values owned by the running closure are
+    /// not dropped.
+    ///
+    /// GC does not see Ruby objects captured by the closure, so they must be wrapped in
+    /// `BoxedValue` to be kept alive until the fiber uses them.
+    ///
+    /// If the closure panics, `RuntimeError` is raised in the fiber.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fiber, Fixnum, NilClass, Object, VM};
+    /// # VM::init();
+    ///
+    /// let fiber = Fiber::new(|_| {
+    ///     for number in 1..3 {
+    ///         Fiber::yield_value(Fixnum::new(number));
+    ///     }
+    ///
+    ///     Fixnum::new(3).to_any_object()
+    /// });
+    ///
+    /// let resume = || fiber.resume(NilClass::new()).try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(resume(), Ok(Fixnum::new(1)));
+    /// assert_eq!(resume(), Ok(Fixnum::new(2)));
+    /// assert_eq!(resume(), Ok(Fixnum::new(3)));
+    ///
+    /// assert!(!fiber.is_alive());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// fiber = Fiber.new do
+    ///   (1...3).each { |number| Fiber.yield(number) }
+    ///
+    ///   3
+    /// end
+    ///
+    /// fiber.resume == 1
+    /// fiber.resume == 2
+    /// fiber.resume == 3
+    ///
+    /// fiber.alive? == false
+    /// ```
+    pub fn new<F>(closure: F) -> Self
+        where F: FnOnce(AnyObject) -> AnyObject + 'static
+    {
+        // Hidden object (without a class) owning the closure, it is marked by the fiber
+        let closure = FiberClosure { closure: Some(Box::new(closure)) };
+        let closure = typed_data::wrap(Value::from(0), closure);
+
+        Self::from(fiber::new(call_closure as CallbackPtr, closure))
+    }
+
+    /// Returns the currently running fiber.
+    pub fn current() -> Self {
+        Self::from(fiber::current())
+    }
+
+    /// Resumes a fiber passing `value` to it.
+    ///
+    /// Returns the value passed to `Fiber::yield_value()` or the result of the fiber's closure
+    /// when it is finished. Raises `FiberError` if the fiber is already finished.
+    pub fn resume<T: Object>(&self, value: T) -> AnyObject {
+        let argv = [value.value()];
+
+        AnyObject::from(fiber::resume(self.value(), 1, argv.as_ptr()))
+    }
+
+    /// Suspends the current fiber and returns `value` to `resume()` of the caller.
+    ///
+    /// Returns the value which is passed to the next `resume()` of the current fiber.
+    pub fn yield_value<T: Object>(value: T) -> AnyObject {
+        let argv = [value.value()];
+
+        AnyObject::from(fiber::yield_values(1, argv.as_ptr()))
+    }
+
+    /// Checks if a fiber can be resumed.
+    pub fn is_alive(&self) -> bool {
+        fiber::is_alive(self.value())
+    }
+}
+
+struct FiberClosure {
+    closure: Option<Box<dyn FnOnce(AnyObject) -> AnyObject>>,
+}
+
+extern "C" fn call_closure(_yielded_argument: Value,
+                           closure: Value,
+                           argc: Argc,
+                           argv: *const AnyObject,
+                           _block: Value)
+                           -> Value {
+    let argument = VM::parse_arguments(argc, argv)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| NilClass::new().to_any_object());
+
+    // The closure is taken out of the hidden object, so it is dropped as soon as it finishes
+    let closure = VM::with_data_mut(&AnyObject::from(closure),
+                                    |closure: &mut FiberClosure| closure.closure.take());
+
+    match closure {
+        Some(closure) => VM::raise_on_panic(|| closure(argument).value()),
+        None => NilClass::new().value(),
+    }
+}
+
+impl From<Value> for Fiber {
+    fn from(value: Value) -> Self {
+        Fiber { value: value, _not_send: PhantomData }
+    }
+}
+
+impl Object for Fiber {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Fiber {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        class::is_kind_of(object.value(), Class::from_existing("Fiber").value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Fiber"
+    }
+}
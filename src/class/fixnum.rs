@@ -1,4 +1,5 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::fixnum;
 use types::{Value, ValueType};
@@ -7,8 +8,10 @@ use {Object, VerifiedObject};
 
 /// `Fixnum`
 #[derive(Debug, PartialEq)]
+#[repr(transparent)]
 pub struct Fixnum {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl Fixnum {
@@ -59,7 +62,7 @@ impl Fixnum {
 
 impl From<Value> for Fixnum {
     fn from(value: Value) -> Self {
-        Fixnum { value: value }
+        Fixnum { value: value, _not_send: PhantomData }
     }
 }
 
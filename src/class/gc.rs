@@ -0,0 +1,37 @@
+use std::marker::PhantomData;
+
+use binding::gc;
+
+/// Keeps the garbage collector disabled, see `VM::gc_disable()`
+///
+/// GC is enabled again when the guard is dropped, unless it was already disabled when
+/// the guard was created.
+#[must_use]
+pub struct GcDisableGuard {
+    was_disabled: bool,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl GcDisableGuard {
+    /// Disables the garbage collector, same as `VM::gc_disable()`.
+    pub fn new() -> Self {
+        GcDisableGuard {
+            was_disabled: gc::disable(),
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Default for GcDisableGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for GcDisableGuard {
+    fn drop(&mut self) {
+        if !self.was_disabled {
+            gc::enable();
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::marker::PhantomData;
+
+/// A proof that the current thread has released the Global VM Lock (GVL)
+///
+/// Ruby objects can only be used by a thread which holds the GVL. Wrappers of Ruby objects
+/// (`AnyObject`, `RString`, `Array` etc) are not `Send`, so they cannot be moved to other
+/// threads:
+///
+/// ```compile_fail
+/// use ruru::{RString, VM};
+/// # VM::init();
+///
+/// let string = RString::new("ruru");
+///
+/// std::thread::spawn(move || string.to_string());
+/// ```
+///
+/// Closures passed to `VM::thread_call_without_gvl()` and similar functions receive `&NoGvl`.
+/// Ruby objects cannot be captured by these closures or returned from them, because the
+/// closures and their results must be `Send`. Ruby API must not be called inside of them,
+/// `VM::thread_call_with_gvl()` takes `&NoGvl` to re-acquire the GVL for that.
+///
+/// ```compile_fail
+/// use ruru::{Fixnum, VM};
+/// # VM::init();
+///
+/// let number = Fixnum::new(1);
+///
+/// VM::thread_call_without_gvl(move |_| number.to_i64(), None::<fn()>);
+/// ```
+///
+/// The token is not `Send` or `Sync`, so it cannot be used by other threads (which do not
+/// belong to Ruby) to acquire the GVL.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ruru::{Fixnum, VM};
+/// # VM::init();
+///
+/// let sum = VM::thread_call_without_gvl(|no_gvl| {
+///     let mut sum = 0;
+///
+///     for step in 0..10 {
+///         sum += step;
+///
+///         VM::thread_call_with_gvl(no_gvl, || {
+///             VM::global_set("$progress", Fixnum::new(step));
+///         });
+///     }
+///
+///     sum
+/// }, None::<fn()>);
+/// ```
+pub struct NoGvl {
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl NoGvl {
+    /// Creates a token for a thread which has released the GVL.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure that the current thread is a Ruby thread which has released
+    /// the GVL and that the token is not used after the GVL is re-acquired.
+    pub unsafe fn assume() -> Self {
+        NoGvl { _not_send: PhantomData }
+    }
+}
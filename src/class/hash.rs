@@ -1,4 +1,5 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::hash;
 use types::{Value, ValueType};
@@ -7,8 +8,10 @@ use {AnyObject, Object, VerifiedObject};
 
 /// `Hash`
 #[derive(Debug, PartialEq)]
+#[repr(transparent)]
 pub struct Hash {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl Hash {
@@ -175,7 +178,7 @@ impl Hash {
 
 impl From<Value> for Hash {
     fn from(value: Value) -> Self {
-        Hash { value: value }
+        Hash { value: value, _not_send: PhantomData }
     }
 }
 
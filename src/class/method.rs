@@ -0,0 +1,326 @@
+use std::convert::From;
+use std::marker::PhantomData;
+
+use binding::method;
+use types::Value;
+use util;
+
+use {AnyObject, Array, Class, Fixnum, Object, Proc, RString, Symbol, VerifiedObject};
+
+/// `Method`
+///
+/// A method bound to its receiver, see `Object::method()`.
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+pub struct Method {
+    value: Value,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl Method {
+    /// Calls a method with given arguments
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let method = RString::new("Hello, ").method("concat");
+    /// let result = method.call(vec![RString::new("World").to_any_object()]);
+    ///
+    /// assert_eq!(result.try_convert_to::<RString>().unwrap().to_string(), "Hello, World");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// 'Hello, '.method(:concat).call('World') == 'Hello, World'
+    /// ```
+    pub fn call(&self, arguments: Vec<AnyObject>) -> AnyObject {
+        let (argc, argv) = util::create_arguments(arguments);
+        let result = method::call(self.value(), argc, argv.as_ptr());
+
+        AnyObject::from(result)
+    }
+
+    /// Returns the name of a method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// let method = RString::new("ruru").method("length");
+    ///
+    /// assert_eq!(method.name(), Symbol::new("length"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// 'ruru'.method(:length).name == :length
+    /// ```
+    pub fn name(&self) -> Symbol {
+        name(self)
+    }
+
+    /// Returns the number of mandatory arguments of a method (Ruby `Method#arity`).
+    ///
+    /// Negative values mean that the method takes optional arguments, `-n - 1` where `n`
+    /// is the number of mandatory arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// assert_eq!(RString::new("ruru").method("length").arity(), 0);
+    /// assert_eq!(RString::new("ruru").method("concat").arity(), -1);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// 'ruru'.method(:length).arity == 0
+    /// 'ruru'.method(:concat).arity == -1
+    /// ```
+    pub fn arity(&self) -> i64 {
+        arity(self)
+    }
+
+    /// Returns the class or module which defines a method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let method = RString::new("ruru").method("length");
+    ///
+    /// assert_eq!(method.owner(), Class::from_existing("String"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// 'ruru'.method(:length).owner == String
+    /// ```
+    pub fn owner(&self) -> Class {
+        owner(self)
+    }
+
+    /// Returns the file name and the line number where a method is defined.
+    ///
+    /// Returns `None` if the method is not defined in Ruby (for example, native methods).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let method = RString::new("ruru").method("length");
+    ///
+    /// assert_eq!(method.source_location(), None);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// 'ruru'.method(:length).source_location == nil
+    /// ```
+    pub fn source_location(&self) -> Option<(String, i64)> {
+        source_location(self)
+    }
+
+    /// Returns the receiver of a method.
+    pub fn receiver(&self) -> AnyObject {
+        self.send("receiver", vec![])
+    }
+
+    /// Detaches a method from its receiver.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let length = RString::new("ruru").method("length").unbind();
+    /// let method = length.bind(&RString::new("Hello"));
+    ///
+    /// assert_eq!(method.receiver().try_convert_to::<RString>().unwrap().to_string(), "Hello");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// length = 'ruru'.method(:length).unbind
+    ///
+    /// length.bind('Hello').receiver == 'Hello'
+    /// ```
+    pub fn unbind(&self) -> UnboundMethod {
+        UnboundMethod::from(self.send("unbind", vec![]).value())
+    }
+
+    /// Converts a method to a lambda.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let length = RString::new("ruru").method("length").to_proc();
+    ///
+    /// assert!(length.is_lambda());
+    /// assert_eq!(length.call(vec![]).try_convert_to::<Fixnum>(), Ok(Fixnum::new(4)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// length = 'ruru'.method(:length).to_proc
+    ///
+    /// length.lambda? == true
+    /// length.call == 4
+    /// ```
+    pub fn to_proc(&self) -> Proc {
+        Proc::from(self.send("to_proc", vec![]).value())
+    }
+}
+
+impl From<Value> for Method {
+    fn from(value: Value) -> Self {
+        Method { value: value, _not_send: PhantomData }
+    }
+}
+
+impl Object for Method {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Method {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        object.class() == Class::from_existing("Method")
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Method"
+    }
+}
+
+/// `UnboundMethod`
+///
+/// A method which is not bound to a receiver, see `Class::instance_method()`.
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+pub struct UnboundMethod {
+    value: Value,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl UnboundMethod {
+    /// Returns the name of a method.
+    pub fn name(&self) -> Symbol {
+        name(self)
+    }
+
+    /// Returns the number of mandatory arguments of a method (Ruby `UnboundMethod#arity`).
+    pub fn arity(&self) -> i64 {
+        arity(self)
+    }
+
+    /// Returns the class or module which defines a method.
+    pub fn owner(&self) -> Class {
+        owner(self)
+    }
+
+    /// Returns the file name and the line number where a method is defined.
+    ///
+    /// Returns `None` if the method is not defined in Ruby (for example, native methods).
+    pub fn source_location(&self) -> Option<(String, i64)> {
+        source_location(self)
+    }
+
+    /// Binds a method to `object` which must be an instance of the method's owner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Fixnum, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let length = Class::from_existing("String").instance_method("length");
+    /// let method = length.bind(&RString::new("ruru"));
+    ///
+    /// assert_eq!(method.call(vec![]).try_convert_to::<Fixnum>(), Ok(Fixnum::new(4)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// String.instance_method(:length).bind('ruru').call == 4
+    /// ```
+    pub fn bind<T: Object>(&self, object: &T) -> Method {
+        Method::from(self.send("bind", vec![object.to_any_object()]).value())
+    }
+}
+
+impl From<Value> for UnboundMethod {
+    fn from(value: Value) -> Self {
+        UnboundMethod { value: value, _not_send: PhantomData }
+    }
+}
+
+impl Object for UnboundMethod {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for UnboundMethod {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        object.class() == Class::from_existing("UnboundMethod")
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to UnboundMethod"
+    }
+}
+
+// Using unsafe conversions is ok, because MRI guarantees the types of results of these methods
+
+fn name<T: Object>(method: &T) -> Symbol {
+    unsafe { method.send("name", vec![]).to::<Symbol>() }
+}
+
+fn arity<T: Object>(method: &T) -> i64 {
+    unsafe { method.send("arity", vec![]).to::<Fixnum>() }.to_i64()
+}
+
+fn owner<T: Object>(method: &T) -> Class {
+    unsafe { method.send("owner", vec![]).to::<Class>() }
+}
+
+fn source_location<T: Object>(method: &T) -> Option<(String, i64)> {
+    let location = method.send("source_location", vec![]);
+
+    if location.is_nil() {
+        return None;
+    }
+
+    let location = unsafe { location.to::<Array>() };
+    let file = unsafe { location.at(0).to::<RString>() }.to_string();
+    let line = unsafe { location.at(1).to::<Fixnum>() }.to_i64();
+
+    Some((file, line))
+}
@@ -1,12 +1,23 @@
 pub mod any_object;
 pub mod array;
 pub mod boolean;
+pub mod boxed_value;
+pub mod cancellation_token;
 pub mod class;
+pub mod enumerator;
+pub mod exception;
+pub mod executor;
+pub mod fiber;
 pub mod fixnum;
+pub mod gc;
+pub mod gvl;
 pub mod hash;
+pub mod method;
 pub mod nil_class;
+pub mod rbinding;
 pub mod rproc;
 pub mod string;
 pub mod traits;
 pub mod symbol;
+pub mod thread;
 pub mod vm;
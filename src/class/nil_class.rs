@@ -1,4 +1,5 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::global::RubySpecialConsts;
 use types::{InternalValue, Value, ValueType};
@@ -7,8 +8,10 @@ use {Object, VerifiedObject};
 
 /// `NilClass`
 #[derive(Debug, PartialEq)]
+#[repr(transparent)]
 pub struct NilClass {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl NilClass {
@@ -35,7 +38,7 @@ impl NilClass {
 
 impl From<Value> for NilClass {
     fn from(value: Value) -> Self {
-        NilClass { value: value }
+        NilClass { value: value, _not_send: PhantomData }
     }
 }
 
@@ -0,0 +1,113 @@
+use std::convert::From;
+use std::marker::PhantomData;
+
+use binding::global::rb_cObject;
+use binding::util as binding_util;
+use types::Value;
+
+use {AnyObject, Class, Object, Symbol, VerifiedObject};
+
+/// `Binding`
+///
+/// An execution context (local variables and `self`) which can be used to evaluate
+/// Ruby code, see `VM::eval_with_binding()`.
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+pub struct Binding {
+    value: Value,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl Binding {
+    /// Returns the binding of the main program (`TOPLEVEL_BINDING`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Binding, Object, VM};
+    /// # VM::init();
+    ///
+    /// let binding = Binding::top_level();
+    ///
+    /// assert_eq!(binding.receiver(), VM::eval("self").unwrap());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// TOPLEVEL_BINDING.receiver == self
+    /// ```
+    pub fn top_level() -> Self {
+        Self::from(binding_util::get_constant("TOPLEVEL_BINDING", unsafe { rb_cObject }))
+    }
+
+    /// Returns the value of a local variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Binding, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let mut binding = Binding::top_level();
+    /// binding.local_variable_set("answer", Fixnum::new(42));
+    ///
+    /// assert_eq!(binding.local_variable_get("answer").try_convert_to::<Fixnum>(),
+    ///            Ok(Fixnum::new(42)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// TOPLEVEL_BINDING.local_variable_set(:answer, 42)
+    ///
+    /// TOPLEVEL_BINDING.local_variable_get(:answer) == 42
+    /// ```
+    pub fn local_variable_get(&self, name: &str) -> AnyObject {
+        self.send("local_variable_get", vec![Symbol::new(name).to_any_object()])
+    }
+
+    /// Sets the value of a local variable.
+    ///
+    /// The variable is created if it does not exist yet.
+    pub fn local_variable_set<T: Object>(&mut self, name: &str, value: T) -> AnyObject {
+        let arguments = vec![Symbol::new(name).to_any_object(), value.to_any_object()];
+
+        self.send("local_variable_set", arguments)
+    }
+
+    /// Checks if a local variable is defined.
+    pub fn is_local_variable_defined(&self, name: &str) -> bool {
+        let name = Symbol::new(name).to_any_object();
+
+        self.send("local_variable_defined?", vec![name]).value().is_true()
+    }
+
+    /// Returns the receiver (`self`) of a binding.
+    pub fn receiver(&self) -> AnyObject {
+        self.send("receiver", vec![])
+    }
+}
+
+impl From<Value> for Binding {
+    fn from(value: Value) -> Self {
+        Binding { value: value, _not_send: PhantomData }
+    }
+}
+
+impl Object for Binding {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Binding {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        object.class() == Class::from_existing("Binding")
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Binding"
+    }
+}
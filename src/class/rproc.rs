@@ -1,18 +1,132 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
-use binding::rproc;
-use types::Value;
+use binding::{rproc, typed_data};
+use types::{Argc, CallbackPtr, Value};
 use util;
 
-use {AnyObject, Class, Object, VerifiedObject};
+use {AnyObject, Array, Class, Fixnum, Object, VerifiedObject, VM};
 
 /// `Proc` (works with `Lambda` as well)
 #[derive(Debug, PartialEq)]
+#[repr(transparent)]
 pub struct Proc {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl Proc {
+    /// Creates a new `Proc` from a Rust closure.
+    ///
+    /// The closure receives all the arguments the proc is called with. It is kept alive
+    /// as long as the proc and is dropped when the proc is garbage collected.
+    ///
+    /// GC does not see Ruby objects captured by the closure, so they must be wrapped in
+    /// `BoxedValue` to be kept alive while the closure is alive.
+    ///
+    /// The closure cannot call its own proc while it is running, a `RuntimeError` is raised
+    /// instead. Panics in the closure are raised as `RuntimeError` too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Hash, Object, Proc, RString, VM};
+    /// # VM::init();
+    ///
+    /// let default_proc = Proc::new(|arguments| {
+    ///     let key = arguments[1].try_convert_to::<RString>().unwrap();
+    ///
+    ///     Fixnum::new(key.to_string().len() as i64).to_any_object()
+    /// });
+    ///
+    /// let hash = Hash::new();
+    /// hash.send("default_proc=", vec![default_proc.to_any_object()]);
+    ///
+    /// let length = hash.at(RString::new("ruru")).try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(length, Ok(Fixnum::new(4)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// hash = Hash.new { |_hash, key| key.length }
+    ///
+    /// hash['ruru'] == 4
+    /// ```
+    ///
+    /// Capturing a Ruby object:
+    ///
+    /// ```
+    /// use ruru::{BoxedValue, Object, Proc, RString, VM};
+    /// # VM::init();
+    ///
+    /// let greeting = BoxedValue::new(RString::new("Hello"));
+    /// let greet = Proc::new(move |_| greeting.to_any_object());
+    ///
+    /// VM::gc_start();
+    ///
+    /// let result = greet.call(vec![]).try_convert_to::<RString>().unwrap();
+    ///
+    /// assert_eq!(result.to_string(), "Hello".to_string());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// greeting = 'Hello'
+    /// greet = proc { greeting }
+    ///
+    /// greet.call == 'Hello'
+    /// ```
+    pub fn new<F>(closure: F) -> Self
+        where F: FnMut(Vec<AnyObject>) -> AnyObject + 'static
+    {
+        let closure = Self::wrap_closure(closure);
+
+        Self::from(rproc::new(call_closure as CallbackPtr, closure))
+    }
+
+    /// Creates a new lambda from a Rust closure.
+    ///
+    /// Works like `Proc::new()`, but the result is a lambda (`lambda?` returns `true`).
+    /// Ruby objects captured by the closure must be wrapped in `BoxedValue` as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Boolean, Fixnum, Object, Proc, VM};
+    /// # VM::init();
+    ///
+    /// let double = Proc::lambda(|arguments| {
+    ///     let number = arguments[0].try_convert_to::<Fixnum>().unwrap();
+    ///
+    ///     Fixnum::new(number.to_i64() * 2).to_any_object()
+    /// });
+    ///
+    /// let is_lambda = double.send("lambda?", vec![]).try_convert_to::<Boolean>();
+    /// let result = double.call(vec![Fixnum::new(21).to_any_object()]).try_convert_to::<Fixnum>();
+    ///
+    /// assert_eq!(is_lambda, Ok(Boolean::new(true)));
+    /// assert_eq!(result, Ok(Fixnum::new(42)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// double = ->(number) { number * 2 }
+    ///
+    /// double.lambda? == true
+    /// double.call(21) == 42
+    /// ```
+    pub fn lambda<F>(closure: F) -> Self
+        where F: FnMut(Vec<AnyObject>) -> AnyObject + 'static
+    {
+        let closure = Self::wrap_closure(closure);
+
+        Self::from(rproc::new_lambda(call_closure as CallbackPtr, closure))
+    }
+
     /// Calls a proc with given arguments
     ///
     /// # Examples
@@ -63,11 +177,232 @@ impl Proc {
 
         AnyObject::from(result)
     }
+
+    /// Calls a proc with given arguments and a block
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, Fixnum, Object, Proc, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// let map = Symbol::new("map").to_proc();
+    ///
+    /// let double = Proc::new(|arguments| {
+    ///     let number = arguments[0].try_convert_to::<Fixnum>().unwrap();
+    ///
+    ///     Fixnum::new(number.to_i64() * 2).to_any_object()
+    /// });
+    ///
+    /// let mut array = Array::new();
+    /// array.push(Fixnum::new(1));
+    /// array.push(Fixnum::new(2));
+    ///
+    /// let result = map.call_with_block(vec![array.to_any_object()], double);
+    /// let result = result.try_convert_to::<Array>().unwrap();
+    ///
+    /// assert_eq!(result.at(0).try_convert_to::<Fixnum>(), Ok(Fixnum::new(2)));
+    /// assert_eq!(result.at(1).try_convert_to::<Fixnum>(), Ok(Fixnum::new(4)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// :map.to_proc.call([1, 2]) { |number| number * 2 } == [2, 4]
+    /// ```
+    pub fn call_with_block(&self, arguments: Vec<AnyObject>, block: Proc) -> AnyObject {
+        let (argc, argv) = util::create_arguments(arguments);
+        let result = rproc::call_with_block(self.value(), argc, argv.as_ptr(), block.value());
+
+        AnyObject::from(result)
+    }
+
+    /// Returns the number of mandatory arguments of a proc (Ruby `Proc#arity`).
+    ///
+    /// Negative values mean that the proc takes optional arguments, `-n - 1` where `n`
+    /// is the number of mandatory arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{NilClass, Object, Proc, VM};
+    /// # VM::init();
+    ///
+    /// let lambda = Proc::lambda(|_| NilClass::new().to_any_object());
+    ///
+    /// assert_eq!(lambda.arity(), -1);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// ->(*arguments) {}.arity == -1
+    /// ```
+    pub fn arity(&self) -> i64 {
+        rproc::arity(self.value()) as i64
+    }
+
+    /// Checks if a proc is a lambda (Ruby `Proc#lambda?`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{NilClass, Object, Proc, VM};
+    /// # VM::init();
+    ///
+    /// let rproc = Proc::new(|_| NilClass::new().to_any_object());
+    /// let lambda = Proc::lambda(|_| NilClass::new().to_any_object());
+    ///
+    /// assert!(!rproc.is_lambda());
+    /// assert!(lambda.is_lambda());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// proc {}.lambda? == false
+    /// lambda {}.lambda? == true
+    /// ```
+    pub fn is_lambda(&self) -> bool {
+        rproc::is_lambda(self.value())
+    }
+
+    /// Returns the parameters of a proc (Ruby `Proc#parameters`).
+    ///
+    /// Each parameter is an `Array` of its kind (`:req`, `:opt`, `:rest`, `:keyreq`, `:key`,
+    /// `:keyrest` or `:block`) and, if the parameter has one, its name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, NilClass, Object, Proc, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// let lambda = Proc::lambda(|_| NilClass::new().to_any_object());
+    /// let parameters = lambda.parameters();
+    /// let first = parameters.at(0).try_convert_to::<Array>().unwrap();
+    ///
+    /// assert_eq!(first.at(0).try_convert_to::<Symbol>(), Ok(Symbol::new("rest")));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// ->(*arguments) {}.parameters == [[:rest, :arguments]]
+    /// ```
+    pub fn parameters(&self) -> Array {
+        Array::from(self.send("parameters", vec![]).value())
+    }
+
+    /// Returns a curried proc (Ruby `Proc#curry`).
+    ///
+    /// The curried proc takes the arguments one by one and calls the original proc when it
+    /// has received `arity` of them. Procs created from Rust closures accept any number of
+    /// arguments and are called right away, use `Proc::curry_with_arity()` for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, Proc, VM};
+    /// # VM::init();
+    ///
+    /// let add = VM::eval("->(left, right) { left + right }").unwrap();
+    /// let add = add.try_convert_to::<Proc>().unwrap().curry();
+    ///
+    /// let add_one = add.call(vec![Fixnum::new(1).to_any_object()]);
+    /// let add_one = add_one.try_convert_to::<Proc>().unwrap();
+    ///
+    /// let result = add_one.call(vec![Fixnum::new(2).to_any_object()]);
+    ///
+    /// assert_eq!(result.try_convert_to::<Fixnum>(), Ok(Fixnum::new(3)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// add = ->(left, right) { left + right }.curry
+    ///
+    /// add[1][2] == 3
+    /// ```
+    pub fn curry(&self) -> Proc {
+        Proc::from(self.send("curry", vec![]).value())
+    }
+
+    /// Returns a curried proc which calls the original proc after receiving `arity` arguments
+    /// (Ruby `Proc#curry(arity)`).
+    ///
+    /// Raises an `ArgumentError` if a lambda does not accept `arity` arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, Proc, VM};
+    /// # VM::init();
+    ///
+    /// let add = Proc::lambda(|arguments| {
+    ///     let sum = arguments
+    ///         .iter()
+    ///         .map(|argument| argument.try_convert_to::<Fixnum>().unwrap().to_i64())
+    ///         .sum();
+    ///
+    ///     Fixnum::new(sum).to_any_object()
+    /// });
+    ///
+    /// let add = add.curry_with_arity(2);
+    ///
+    /// let add_one = add.call(vec![Fixnum::new(1).to_any_object()]);
+    /// let add_one = add_one.try_convert_to::<Proc>().unwrap();
+    ///
+    /// let result = add_one.call(vec![Fixnum::new(2).to_any_object()]);
+    ///
+    /// assert_eq!(result.try_convert_to::<Fixnum>(), Ok(Fixnum::new(3)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// add = ->(*numbers) { numbers.sum }.curry(2)
+    ///
+    /// add[1][2] == 3
+    /// ```
+    pub fn curry_with_arity(&self, arity: i64) -> Proc {
+        let arity = Fixnum::new(arity).to_any_object();
+
+        Proc::from(self.send("curry", vec![arity]).value())
+    }
+
+    fn wrap_closure<F>(closure: F) -> Value
+        where F: FnMut(Vec<AnyObject>) -> AnyObject + 'static
+    {
+        // Hidden object (without a class) owning the closure, it is marked by the proc.
+        // Objects captured by the closure are not marked.
+        let closure = ProcClosure { closure: Box::new(closure) };
+
+        typed_data::wrap(Value::from(0), closure)
+    }
+}
+
+struct ProcClosure {
+    closure: Box<dyn FnMut(Vec<AnyObject>) -> AnyObject>,
+}
+
+extern "C" fn call_closure(_yielded_argument: Value,
+                           closure: Value,
+                           argc: Argc,
+                           argv: *const AnyObject,
+                           _block: Value)
+                           -> Value {
+    let arguments = VM::parse_arguments(argc, argv);
+    let closure = AnyObject::from(closure);
+
+    // Raises if the closure calls its own proc or panics
+    VM::with_data_mut(&closure,
+                      |closure: &mut ProcClosure| (closure.closure)(arguments).value())
 }
 
 impl From<Value> for Proc {
     fn from(value: Value) -> Self {
-        Proc { value: value }
+        Proc { value: value, _not_send: PhantomData }
     }
 }
 
@@ -1,4 +1,5 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::string;
 use types::{Value, ValueType};
@@ -7,8 +8,10 @@ use {Object, VerifiedObject};
 
 /// `String`
 #[derive(Debug, PartialEq)]
+#[repr(transparent)]
 pub struct RString {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl RString {
@@ -119,7 +122,7 @@ impl RString {
 
 impl From<Value> for RString {
     fn from(value: Value) -> Self {
-        RString { value: value }
+        RString { value: value, _not_send: PhantomData }
     }
 }
 
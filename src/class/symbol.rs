@@ -1,15 +1,18 @@
 use std::convert::From;
+use std::marker::PhantomData;
 
 use binding::symbol;
 use binding::util;
 use types::{Value, ValueType};
 
-use {Object, VerifiedObject};
+use {Object, Proc, VerifiedObject};
 
 /// `Symbol`
 #[derive(Debug, PartialEq)]
+#[repr(transparent)]
 pub struct Symbol {
     value: Value,
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl Symbol {
@@ -64,11 +67,35 @@ impl Symbol {
 
         symbol::id_to_name(id)
     }
+
+    /// Converts a symbol to a proc which calls the method with the same name on its first
+    /// argument (Ruby `Symbol#to_proc`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// let upcase = Symbol::new("upcase").to_proc();
+    /// let result = upcase.call(vec![RString::new("ruru").to_any_object()]);
+    ///
+    /// assert_eq!(result.try_convert_to::<RString>().unwrap().to_string(), "RURU");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// :upcase.to_proc.call('ruru') == 'RURU'
+    /// ```
+    pub fn to_proc(&self) -> Proc {
+        Proc::from(self.send("to_proc", vec![]).value())
+    }
 }
 
 impl From<Value> for Symbol {
     fn from(value: Value) -> Self {
-        Symbol { value: value }
+        Symbol { value: value, _not_send: PhantomData }
     }
 }
 
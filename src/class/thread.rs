@@ -0,0 +1,226 @@
+use std::convert::From;
+use std::marker::PhantomData;
+use std::ptr;
+
+use binding::{class, thread, vm};
+use binding::util as binding_util;
+use types::Value;
+
+use {AnyObject, Class, Exception, Object, VerifiedObject, VM};
+
+/// `Thread`
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+pub struct Thread {
+    value: Value,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl Thread {
+    /// Creates a new Ruby thread which runs `func`.
+    ///
+    /// The thread holds the GVL while `func` runs, like any other Ruby thread. The value
+    /// returned by `func` becomes the value of the thread. If `func` panics, the thread
+    /// is terminated by a `RuntimeError`.
+    ///
+    /// `func` must be `Send`, so it cannot capture Ruby objects, see `new_unchecked()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let thread = Thread::new(|| Fixnum::new(1 + 2));
+    ///
+    /// assert_eq!(thread.join_value().unwrap().try_convert_to::<Fixnum>(), Ok(Fixnum::new(3)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.new { 1 + 2 }
+    ///
+    /// thread.value == 3
+    /// ```
+    pub fn new<F, R>(func: F) -> Self
+        where F: FnOnce() -> R + Send + 'static,
+              R: Object
+    {
+        unsafe { Self::new_unchecked(func) }
+    }
+
+    /// Creates a new Ruby thread which runs `func` which may capture Ruby objects.
+    ///
+    /// Behaves like `new()`, but does not require `func` to be `Send`.
+    ///
+    /// # Safety
+    ///
+    /// GC does not see Ruby objects captured by `func` until the thread starts running it,
+    /// so the caller must keep them alive (for example, with `BoxedValue`). `func` must not
+    /// capture values which can not be used by another thread holding the GVL (for example,
+    /// `Rc` shared with the current thread).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{BoxedValue, Object, RString, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let name = BoxedValue::new(RString::new("ruru"));
+    ///
+    /// let thread = unsafe {
+    ///     Thread::new_unchecked(move || RString::new(&format!("{}!", name.to_string())))
+    /// };
+    ///
+    /// let value = thread.join_value().unwrap().try_convert_to::<RString>().unwrap();
+    ///
+    /// assert_eq!(value.to_string(), "ruru!".to_string());
+    /// ```
+    pub unsafe fn new_unchecked<F, R>(func: F) -> Self
+        where F: FnOnce() -> R + 'static,
+              R: Object
+    {
+        Self::from(thread::create(move || VM::raise_on_panic(|| func().value())))
+    }
+
+    /// Returns the currently running thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let main = Thread::current();
+    ///
+    /// assert!(main.is_alive());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// Thread.current.alive? == true
+    /// ```
+    pub fn current() -> Self {
+        Self::from(thread::current())
+    }
+
+    /// Waits for a thread to finish.
+    ///
+    /// Returns the exception if the thread was terminated by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Boolean, Class, NilClass, Object, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let thread = Thread::new(|| {
+    ///     VM::raise(Class::from_existing("ArgumentError"), "Wrong argument");
+    ///
+    ///     NilClass::new()
+    /// });
+    ///
+    /// thread.send("report_on_exception=", vec![Boolean::new(false).to_any_object()]);
+    ///
+    /// let exception = thread.join().unwrap_err();
+    ///
+    /// assert_eq!(exception.class(), Class::from_existing("ArgumentError"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// thread = Thread.new { raise ArgumentError, 'Wrong argument' }
+    ///
+    /// begin
+    ///   thread.join
+    /// rescue ArgumentError
+    /// end
+    /// ```
+    pub fn join(&self) -> Result<(), Exception> {
+        self.call_protected("join").map(|_| ())
+    }
+
+    /// Waits for a thread to finish and returns its value (Ruby `Thread#value`).
+    ///
+    /// Returns the exception if the thread was terminated by one.
+    pub fn join_value(&self) -> Result<AnyObject, Exception> {
+        self.call_protected("value")
+    }
+
+    /// Checks if a thread is running or sleeping.
+    pub fn is_alive(&self) -> bool {
+        self.send("alive?", vec![]).value().is_true()
+    }
+
+    /// Returns the value of a fiber-local variable of a thread (Ruby `Thread#[]`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, Thread, VM};
+    /// # VM::init();
+    ///
+    /// let mut thread = Thread::current();
+    ///
+    /// thread.local_set("request_id", RString::new("42"));
+    ///
+    /// let request_id = thread.local_get("request_id").try_convert_to::<RString>().unwrap();
+    ///
+    /// assert_eq!(request_id.to_string(), "42");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// Thread.current[:request_id] = '42'
+    ///
+    /// Thread.current[:request_id] == '42'
+    /// ```
+    pub fn local_get(&self, name: &str) -> AnyObject {
+        AnyObject::from(thread::local_get(self.value(), name))
+    }
+
+    /// Sets the value of a fiber-local variable of a thread (Ruby `Thread#[]=`).
+    pub fn local_set<T: Object>(&mut self, name: &str, value: T) -> AnyObject {
+        AnyObject::from(thread::local_set(self.value(), name, value.value()))
+    }
+
+    /// Passes the GVL to other threads (Ruby `Thread.pass`).
+    pub fn schedule() {
+        thread::schedule();
+    }
+
+    fn call_protected(&self, method: &str) -> Result<AnyObject, Exception> {
+        let thread = self.value();
+
+        vm::rescue(|| binding_util::call_method(thread, method, 0, ptr::null()))
+            .map(AnyObject::from)
+            .map_err(Exception::from)
+    }
+}
+
+impl From<Value> for Thread {
+    fn from(value: Value) -> Self {
+        Thread { value: value, _not_send: PhantomData }
+    }
+}
+
+impl Object for Thread {
+    #[inline]
+    fn value(&self) -> Value {
+        self.value
+    }
+}
+
+impl VerifiedObject for Thread {
+    fn is_correct_type<T: Object>(object: &T) -> bool {
+        class::is_kind_of(object.value(), Class::from_existing("Thread").value())
+    }
+
+    fn error_message() -> &'static str {
+        "Error converting to Thread"
+    }
+}
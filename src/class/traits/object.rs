@@ -1,13 +1,17 @@
+use std::any::Any;
+use std::cell::RefCell;
 use std::convert::From;
+use std::ptr;
+use std::result::Result as StdResult;
 
-use binding::class;
+use binding::{class, method, symbol, typed_data, vm};
 use binding::global::ValueType;
 use binding::util as binding_util;
 use result::{Error, Result};
-use types::{Callback, Value};
+use types::{Callback, FixedMethod, Value};
 use util;
 
-use {AnyObject, Class, VerifiedObject};
+use {AnyObject, Class, Exception, Fixnum, Hash, Method, NilClass, Proc, RString, VerifiedObject};
 
 /// `Object`
 ///
@@ -464,6 +468,232 @@ pub trait Object: From<Value> {
         class::define_singleton_method(self.value(), name, callback);
     }
 
+    /// Defines a private instance method for the given class or object.
+    ///
+    /// Private methods can be called only without an explicit receiver, which is useful for
+    /// helpers which are not a part of the public API of the class.
+    ///
+    /// Use `methods!` macro to define a `callback`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Fixnum, Object, RString, VM};
+    ///
+    /// class!(Counter);
+    ///
+    /// methods!(
+    ///     Counter,
+    ///     itself,
+    ///
+    ///     fn counter_step() -> Fixnum {
+    ///         Fixnum::new(2)
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Counter", None).define(|itself| {
+    ///         itself.define_private_method("step", counter_step);
+    ///     });
+    ///
+    ///     let counter = Class::from_existing("Counter").new_instance(vec![]);
+    ///
+    ///     assert!(!counter.respond_to("step"));
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Counter
+    ///   private
+    ///
+    ///   def step
+    ///     2
+    ///   end
+    /// end
+    ///
+    /// Counter.new.respond_to?(:step) == false
+    /// ```
+    fn define_private_method<I: Object, O: Object>(&mut self,
+                                                   name: &str,
+                                                   callback: Callback<I, O>) {
+        class::define_private_method(self.value(), name, callback);
+    }
+
+    /// Defines a protected instance method for the given class or object.
+    ///
+    /// Protected methods can be called only by instances of the class and its subclasses.
+    ///
+    /// Use `methods!` macro to define a `callback`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Fixnum, Object, VM};
+    ///
+    /// class!(Account);
+    ///
+    /// methods!(
+    ///     Account,
+    ///     itself,
+    ///
+    ///     fn account_balance() -> Fixnum {
+    ///         Fixnum::new(100)
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Account", None).define(|itself| {
+    ///         itself.define_protected_method("balance", account_balance);
+    ///     });
+    ///
+    ///     let account = Class::from_existing("Account").new_instance(vec![]);
+    ///
+    ///     assert!(!account.respond_to("balance"));
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Account
+    ///   protected
+    ///
+    ///   def balance
+    ///     100
+    ///   end
+    /// end
+    /// ```
+    fn define_protected_method<I: Object, O: Object>(&mut self,
+                                                     name: &str,
+                                                     callback: Callback<I, O>) {
+        class::define_protected_method(self.value(), name, callback);
+    }
+
+    /// Defines a module function for the given module (similar to Ruby `module_function`).
+    ///
+    /// The function is defined as a singleton method of the module and as a private instance
+    /// method, so it is also available in classes which include the module.
+    ///
+    /// Use `methods!` macro to define a `callback`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Fixnum, Object, VM};
+    ///
+    /// methods!(
+    ///     Class,
+    ///     itself,
+    ///
+    ///     fn answer() -> Fixnum {
+    ///         Fixnum::new(42)
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::from_existing("Kernel").define(|itself| {
+    ///         itself.define_module_function("answer", answer);
+    ///     });
+    ///
+    ///     let kernel = Class::from_existing("Kernel");
+    ///     let result = kernel.send("answer", vec![]).try_convert_to::<Fixnum>();
+    ///
+    ///     assert_eq!(result, Ok(Fixnum::new(42)));
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// module Kernel
+    ///   module_function
+    ///
+    ///   def answer
+    ///     42
+    ///   end
+    /// end
+    ///
+    /// Kernel.answer == 42
+    /// ```
+    fn define_module_function<I: Object, O: Object>(&mut self,
+                                                    name: &str,
+                                                    callback: Callback<I, O>) {
+        class::define_module_function(self.value(), name, callback);
+    }
+
+    /// Defines an instance method with a fixed number of arguments.
+    ///
+    /// Use `fixed_methods!` macro to define a `callback`. Ruby checks the number of arguments
+    /// itself and `Method#arity` returns the exact number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Fixnum, Object, VM};
+    ///
+    /// class!(Calculator);
+    ///
+    /// fixed_methods!(
+    ///     Calculator,
+    ///     itself,
+    ///
+    ///     fn calculator_add(left: Fixnum, right: Fixnum) -> Fixnum {
+    ///         Fixnum::new(left.unwrap().to_i64() + right.unwrap().to_i64())
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     # VM::init();
+    ///     Class::new("Calculator", None).define(|itself| {
+    ///         itself.define_fixed_method("add", calculator_add);
+    ///     });
+    ///
+    ///     let calculator = Class::from_existing("Calculator").new_instance(vec![]);
+    ///
+    ///     assert_eq!(calculator.method("add").arity(), 2);
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Calculator
+    ///   def add(left, right)
+    ///     left + right
+    ///   end
+    /// end
+    ///
+    /// Calculator.new.method(:add).arity == 2
+    /// ```
+    fn define_fixed_method(&mut self, name: &str, callback: fn() -> FixedMethod) {
+        let method = callback();
+
+        class::define_fixed_method(self.value(), name, method.callback(), method.arity());
+    }
+
+    /// Defines a class method for given class or singleton method for object with a fixed
+    /// number of arguments.
+    ///
+    /// Use `fixed_methods!` macro to define a `callback`.
+    fn define_fixed_singleton_method(&mut self, name: &str, callback: fn() -> FixedMethod) {
+        let method = callback();
+
+        class::define_fixed_singleton_method(self.value(), name, method.callback(), method.arity());
+    }
+
     /// An alias for `define_method` (similar to Ruby syntax `def some_method`).
     fn def<I: Object, O: Object>(&mut self, name: &str, callback: Callback<I, O>) {
         self.define_method(name, callback);
@@ -474,6 +704,26 @@ pub trait Object: From<Value> {
         self.define_singleton_method(name, callback);
     }
 
+    /// An alias for `define_private_method` (similar to Ruby `private def some_method`).
+    fn def_private<I: Object, O: Object>(&mut self, name: &str, callback: Callback<I, O>) {
+        self.define_private_method(name, callback);
+    }
+
+    /// An alias for `define_protected_method` (similar to Ruby `protected def some_method`).
+    fn def_protected<I: Object, O: Object>(&mut self, name: &str, callback: Callback<I, O>) {
+        self.define_protected_method(name, callback);
+    }
+
+    /// An alias for `define_fixed_method`.
+    fn def_fixed(&mut self, name: &str, callback: fn() -> FixedMethod) {
+        self.define_fixed_method(name, callback);
+    }
+
+    /// An alias for `define_fixed_singleton_method`.
+    fn def_self_fixed(&mut self, name: &str, callback: fn() -> FixedMethod) {
+        self.define_fixed_singleton_method(name, callback);
+    }
+
     /// Calls a given method on an object similarly to Ruby `Object#send` method
     ///
     /// # Examples
@@ -500,6 +750,52 @@ pub trait Object: From<Value> {
         AnyObject::from(result)
     }
 
+    /// Calls a given method on an object passing `keywords` as keyword arguments
+    ///
+    /// Requires Ruby 2.7 or newer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Array, Boolean, Hash, Object, RString, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// let mut keywords = Hash::new();
+    ///
+    /// keywords.store(Symbol::new("chomp"), Boolean::new(true));
+    ///
+    /// let lines =
+    ///     RString::new("first\nsecond\n")
+    ///         .send_with_kwargs("lines", vec![], keywords)
+    ///         .try_convert_to::<Array>()
+    ///         .unwrap();
+    ///
+    /// assert_eq!(lines.at(0).try_convert_to::<RString>().unwrap().to_string(), "first");
+    /// assert_eq!(lines.at(1).try_convert_to::<RString>().unwrap().to_string(), "second");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// "first\nsecond\n".lines(chomp: true) == ['first', 'second']
+    /// ```
+    fn send_with_kwargs(&self,
+                        method: &str,
+                        arguments: Vec<AnyObject>,
+                        keywords: Hash)
+                        -> AnyObject {
+        let mut arguments = arguments;
+
+        arguments.push(keywords.to_any_object());
+
+        let (argc, argv) = util::create_arguments(arguments);
+
+        let result =
+            binding_util::call_method_with_kwargs(self.value(), method, argc, argv.as_ptr());
+
+        AnyObject::from(result)
+    }
+
     /// Checks whether the object responds to given method
     ///
     /// # Examples
@@ -517,6 +813,145 @@ pub trait Object: From<Value> {
         class::respond_to(self.value(), method)
     }
 
+    /// Returns a `Method` object for the method with given name.
+    ///
+    /// Raises `NameError` if the object does not respond to the method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let length = RString::new("ruru").method("length");
+    ///
+    /// assert_eq!(length.call(vec![]).try_convert_to::<Fixnum>(), Ok(Fixnum::new(4)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// 'ruru'.method(:length).call == 4
+    /// ```
+    fn method(&self, name: &str) -> Method {
+        let name = symbol::id_to_sym(binding_util::internal_id(name));
+
+        Method::from(method::object_method(self.value(), name))
+    }
+
+    /// Evaluates a string of Ruby code with the object as `self`.
+    ///
+    /// Returns the exception if the code raises one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let length = RString::new("ruru").instance_eval("length").unwrap();
+    ///
+    /// assert_eq!(length.try_convert_to::<Fixnum>(), Ok(Fixnum::new(4)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// 'ruru'.instance_eval('length') == 4
+    /// ```
+    fn instance_eval(&self, code: &str) -> StdResult<AnyObject, Exception> {
+        let receiver = self.value();
+        let code = RString::new(code).value();
+
+        vm::rescue(|| binding_util::call_method(receiver, "instance_eval", 1, &code))
+            .map(AnyObject::from)
+            .map_err(Exception::from)
+    }
+
+    /// Evaluates a string of Ruby code with the object as `self`, reporting `file` and `line`
+    /// as its location.
+    ///
+    /// `file` and `line` are used in backtraces and `__FILE__`/`__LINE__` of the evaluated code.
+    ///
+    /// Returns the exception if the code raises one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let string = RString::new("ruru");
+    /// let exception = string.instance_eval_at("\nfail", "string.rb", 5).unwrap_err();
+    ///
+    /// assert!(exception.backtrace().unwrap()[0].starts_with("string.rb:6"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// 'ruru'.instance_eval("\nfail", 'string.rb', 5)
+    /// ```
+    fn instance_eval_at(&self, code: &str, file: &str, line: i64)
+                        -> StdResult<AnyObject, Exception> {
+        let receiver = self.value();
+        let arguments = vec![RString::new(code).to_any_object(),
+                             RString::new(file).to_any_object(),
+                             Fixnum::new(line).to_any_object()];
+        let (argc, argv) = util::create_arguments(arguments);
+
+        vm::rescue(|| binding_util::call_method(receiver, "instance_eval", argc, argv.as_ptr()))
+            .map(AnyObject::from)
+            .map_err(Exception::from)
+    }
+
+    /// Calls a closure with the object as `self` of the Ruby frame, like `instance_eval`
+    /// with a block.
+    ///
+    /// The closure receives the object as its argument. Returns the exception if the closure
+    /// raises one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let result = RString::new("ruru").instance_eval_with(|itself| {
+    ///     itself.send("upcase", vec![])
+    /// });
+    ///
+    /// assert_eq!(result.unwrap().try_convert_to::<RString>().unwrap().to_string(), "RURU");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// 'ruru'.instance_eval { |itself| itself.upcase } == 'RURU'
+    /// ```
+    fn instance_eval_with<F>(&self, mut func: F) -> StdResult<AnyObject, Exception>
+        where F: FnMut(AnyObject) -> AnyObject + 'static
+    {
+        let receiver = self.value();
+        let block = Proc::new(move |arguments: Vec<AnyObject>| {
+            let itself = arguments.into_iter()
+                .next()
+                .unwrap_or_else(|| NilClass::new().to_any_object());
+
+            func(itself)
+        });
+
+        vm::rescue(|| {
+            binding_util::call_method_with_block(receiver,
+                                                 "instance_eval",
+                                                 0,
+                                                 ptr::null(),
+                                                 block.value())
+        })
+            .map(AnyObject::from)
+            .map_err(Exception::from)
+    }
+
     /// Checks whether the object is `nil`
     ///
     /// # Examples
@@ -676,6 +1111,48 @@ pub trait Object: From<Value> {
         AnyObject::from(result)
     }
 
+    /// Returns Rust data wrapped into the object with `Class::wrap_data()`
+    ///
+    /// Returns `None` if the wrapped data has another type. Raises a `TypeError` if the object
+    /// does not wrap Rust data.
+    ///
+    /// The data is stored in a `RefCell`, it must be borrowed to be accessed. Callbacks should
+    /// use `VM::with_data()` and `VM::with_data_mut()` instead, which also handle re-entered
+    /// callbacks, panics and exceptions.
+    ///
+    /// # Safety
+    ///
+    /// The data is freed when the Ruby object is garbage collected, but the returned reference
+    /// is bound only to the wrapper. The caller must make sure that the object is reachable by
+    /// GC (for example, the wrapper is on the stack or the object is referenced from Ruby)
+    /// while the reference is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// struct Counter {
+    ///     count: i64,
+    /// }
+    ///
+    /// let counter = Class::new("Counter", None).wrap_data(Counter { count: 1 });
+    ///
+    /// if let Some(data) = unsafe { counter.get_data::<Counter>() } {
+    ///     data.borrow_mut().count += 1;
+    /// }
+    ///
+    /// let count = unsafe { counter.get_data::<Counter>() }.map(|counter| counter.borrow().count);
+    ///
+    /// assert_eq!(count, Some(2));
+    /// ```
+    unsafe fn get_data<T: Any>(&self) -> Option<&RefCell<T>> {
+        let value = self.value();
+
+        typed_data::get::<T>(&value).map(|data| &*(data as *const RefCell<T>))
+    }
+
     /// Unsafely casts current object to the specified Ruby type
     ///
     /// This operation in unsafe, because it does not perform any validations on the object, but
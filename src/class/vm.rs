@@ -1,9 +1,20 @@
+use std::any::Any;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
 use std::slice;
 
-use binding::vm;
-use types::{Argc, Value};
+use binding::global::{self, RubySpecialConsts, VariableSetter};
+use binding::{gc, hash, thread, typed_data, vm};
+use binding::util as binding_util;
+use types::{Argc, CallbackPtr, c_int, c_void, Id, InternalValue, Value};
 
-use {AnyObject, Class, Object, Proc};
+use class::cancellation_token::cancel_token;
+use class::executor;
+use util;
+
+use {AnyObject, Binding, CancellationToken, Class, Exception, Fixnum, GcDisableGuard, NoGvl,
+     Object, Proc, RString};
 
 /// Virtual Machine and helpers
 pub struct VM;
@@ -23,6 +34,10 @@ impl VM {
     /// If you write a library which is being connected to Ruby in runtime (e.g. some gem), this
     /// function should not be used.
     ///
+    /// `VM::init()` does not set up `$LOAD_PATH` and never shuts the VM down, use
+    /// `ruru::embed::VmBuilder` to configure the VM for such applications. It does nothing if
+    /// the VM has already been started (by `VM::init()` or `VmBuilder::init()`).
+    ///
     /// # Examples
     ///
     /// ```
@@ -41,70 +56,751 @@ impl VM {
 
     /// Requires Ruby source file.
     ///
-    /// # Examples
+    /// Returns `true` if the file has been loaded and `false` if it was already loaded before.
+    /// Returns the exception if the file cannot be found or raises an exception (including
+    /// `SyntaxError`) while being loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// let exception = VM::require("some_missing_file").unwrap_err();
+    ///
+    /// assert_eq!(exception.class(), Class::from_existing("LoadError"));
+    /// ```
+    ///
+    /// ```no_run
+    /// use ruru::VM;
+    /// # VM::init();
+    ///
+    /// assert_eq!(VM::require("some_ruby_file").unwrap(), true);
+    /// assert_eq!(VM::require("some_ruby_file").unwrap(), false);
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// require 'some_ruby_file' == true
+    /// require 'some_ruby_file' == false
+    /// ```
+    pub fn require(name: &str) -> Result<bool, Exception> {
+        vm::rescue(|| vm::require(name))
+            .map(|loaded| loaded.is_true())
+            .map_err(Exception::from)
+    }
+
+    /// Requires Ruby source file relative to the file of the Ruby code which called
+    /// the current method.
+    ///
+    /// Returns the exception if there is no calling Ruby code (for example, when it is called
+    /// directly from an embedding application) or if `VM::require()` would return one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate ruru;
+    ///
+    /// use ruru::{Boolean, Class, Object, RString, VM};
+    ///
+    /// class!(Plugins);
+    ///
+    /// methods!(
+    ///     Plugins,
+    ///     itself,
+    ///
+    ///     fn load_plugin(name: RString) -> Boolean {
+    ///         let path = format!("plugins/{}", name.unwrap().to_string());
+    ///
+    ///         Boolean::new(VM::require_relative(&path).unwrap())
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     Class::new("Plugins", None).define(|itself| {
+    ///         itself.def_self("load_plugin", load_plugin);
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// # app/main.rb, loads app/plugins/logger.rb
+    /// Plugins.load_plugin('logger')
+    /// ```
+    pub fn require_relative(name: &str) -> Result<bool, Exception> {
+        let kernel = Class::from_existing("Kernel").value();
+        let name = RString::new(name).value();
+
+        vm::rescue(|| binding_util::call_method(kernel, "require_relative", 1, &name))
+            .map(|loaded| loaded.is_true())
+            .map_err(Exception::from)
+    }
+
+    /// Loads and runs Ruby source file every time it is called.
+    ///
+    /// If `wrap` is `true`, the file is run in an anonymous module, so its constants and
+    /// methods do not pollute the global namespace.
+    ///
+    /// Returns the exception if the file cannot be found or raises an exception.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ruru::VM;
+    /// # VM::init();
+    ///
+    /// VM::load("plugins/logger.rb", true).unwrap();
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// load 'plugins/logger.rb', true
+    /// ```
+    pub fn load(path: &str, wrap: bool) -> Result<(), Exception> {
+        vm::load(RString::new(path).value(), wrap).map_err(Exception::from)
+    }
+
+    /// Evaluates a string of Ruby code at the top level.
+    ///
+    /// Returns the exception if the code raises one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let result = VM::eval("1 + 2").unwrap();
+    ///
+    /// assert_eq!(result.try_convert_to::<Fixnum>(), Ok(Fixnum::new(3)));
+    ///
+    /// let exception = VM::eval("1 / 0").unwrap_err();
+    ///
+    /// assert_eq!(exception.class(), Class::from_existing("ZeroDivisionError"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// eval('1 + 2') == 3
+    ///
+    /// begin
+    ///   eval('1 / 0')
+    /// rescue ZeroDivisionError
+    /// end
+    /// ```
+    pub fn eval(code: &str) -> Result<AnyObject, Exception> {
+        vm::eval_string(code).map(AnyObject::from).map_err(Exception::from)
+    }
+
+    /// Evaluates a string of Ruby code at the top level, reporting `file` and `line` as its
+    /// location.
+    ///
+    /// `file` and `line` are used in backtraces and `__FILE__`/`__LINE__` of the evaluated code.
+    ///
+    /// Returns the exception if the code raises one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let file = VM::eval_at("__FILE__", "config.rb", 1).unwrap();
+    ///
+    /// assert_eq!(file.try_convert_to::<RString>().unwrap().to_string(), "config.rb".to_string());
+    ///
+    /// let exception = VM::eval_at("\nraise 'oops'", "config.rb", 10).unwrap_err();
+    ///
+    /// assert!(exception.backtrace().unwrap()[0].starts_with("config.rb:11"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// eval('__FILE__', TOPLEVEL_BINDING, 'config.rb', 1) == 'config.rb'
+    /// ```
+    pub fn eval_at(code: &str, file: &str, line: i64) -> Result<AnyObject, Exception> {
+        Self::eval_with_binding(code, &Binding::top_level(), file, line)
+    }
+
+    /// Evaluates a string of Ruby code in the context of `binding`.
+    ///
+    /// `file` and `line` are used in backtraces and `__FILE__`/`__LINE__` of the evaluated code.
+    ///
+    /// Returns the exception if the code raises one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Binding, Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let mut binding = Binding::top_level();
+    /// binding.local_variable_set("count", Fixnum::new(2));
+    ///
+    /// let result = VM::eval_with_binding("count * 21", &binding, "template.erb", 1).unwrap();
+    ///
+    /// assert_eq!(result.try_convert_to::<Fixnum>(), Ok(Fixnum::new(42)));
+    ///
+    /// let exception = VM::eval_with_binding("\nraise 'oops'", &binding, "template.erb", 10)
+    ///     .unwrap_err();
+    ///
+    /// assert!(exception.backtrace().unwrap()[0].starts_with("template.erb:11"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// binding.local_variable_set(:count, 2)
+    ///
+    /// eval('count * 21', binding, 'template.erb', 1) == 42
+    /// ```
+    pub fn eval_with_binding(code: &str,
+                             binding: &Binding,
+                             file: &str,
+                             line: i64)
+                             -> Result<AnyObject, Exception> {
+        let arguments = vec![RString::new(code).to_any_object(),
+                             binding.to_any_object(),
+                             RString::new(file).to_any_object(),
+                             Fixnum::new(line).to_any_object()];
+
+        let (argc, argv) = util::create_arguments(arguments);
+        let kernel = Class::from_existing("Kernel");
+
+        vm::rescue(|| binding_util::call_method(kernel.value(), "eval", argc, argv.as_ptr()))
+            .map(AnyObject::from)
+            .map_err(Exception::from)
+    }
+
+    /// Raises an exception.
+    ///
+    /// # Examples
+    ///
+    /// ### Built-in exceptions
+    ///
+    /// ```no_run
+    /// use ruru::{Class, VM};
+    /// # VM::init();
+    ///
+    /// VM::raise(Class::from_existing("ArgumentError"), "Wrong argument");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// raise ArgumentError, 'Wrong argument'
+    /// ```
+    ///
+    /// ### Custom exceptions
+    ///
+    /// ```no_run
+    /// use ruru::{Class, VM};
+    /// # VM::init();
+    ///
+    /// let standard_error = Class::from_existing("StandardError");
+    /// let custom_exception = Class::new("CustomException", Some(&standard_error));
+    ///
+    /// VM::raise(custom_exception, "Something went wrong");
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class CustomException < StandardError
+    /// end
+    ///
+    /// raise CustomException, 'Something went wrong'
+    /// ```
+    pub fn raise(exception: Class, message: &str) {
+        vm::raise(exception.value(), message);
+    }
+
+    /// Runs `func` and raises a `RuntimeError` if it panics
+    ///
+    /// Rust panics must not unwind into Ruby C code, use this function to convert them
+    /// into Ruby exceptions in callbacks which may panic. `init!` macro uses it for
+    /// the entry point of an extension.
+    ///
+    /// Returns the value returned by `func`. The panic payload is dropped before
+    /// the exception is raised.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ruru::VM;
+    /// # VM::init();
+    ///
+    /// // Raises `RuntimeError: Something went wrong`
+    /// VM::raise_on_panic(|| panic!("Something went wrong"));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// raise RuntimeError, 'Something went wrong'
+    /// ```
+    pub fn raise_on_panic<F, R>(func: F) -> R
+        where F: FnOnce() -> R
+    {
+        let exception = match panic::catch_unwind(AssertUnwindSafe(func)) {
+            Ok(result) => return result,
+            Err(payload) => Self::panic_exception(payload),
+        };
+
+        exception.raise()
+    }
+
+    fn panic_exception(payload: Box<dyn Any + Send>) -> Exception {
+        let message = Self::panic_message(payload);
+
+        Exception::new(&Class::from_existing("RuntimeError"), &message)
+    }
+
+    fn panic_message(payload: Box<dyn Any + Send>) -> String {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Ok(message) = payload.downcast::<String>() {
+            *message
+        } else {
+            "Rust panic".to_string()
+        }
+    }
+
+    /// Runs `func` with a shared reference to Rust data wrapped into `object` with
+    /// `Class::wrap_data()`.
+    ///
+    /// This function is a helper for callbacks which access wrapped data.
+    ///
+    /// Raises a `TypeError` if the object wraps data of another type and a `RuntimeError` if
+    /// the data is mutably borrowed by a callback which is still running (the callback was
+    /// re-entered) or if `func` panics. Exceptions raised inside of `func` are re-raised after
+    /// the data is released.
+    pub fn with_data<O, T, F, R>(object: &O, func: F) -> R
+        where O: Object,
+              T: Any,
+              F: FnOnce(&T) -> R
+    {
+        let value = object.value();
+
+        let result = match typed_data::get::<T>(&value) {
+            Some(data) => {
+                match data.try_borrow() {
+                    Ok(data) => Self::protect_callback(|| func(&data)),
+                    Err(_) => Err(CallbackError::Borrowed),
+                }
+            }
+            None => Err(CallbackError::WrongType),
+        };
+
+        Self::unwrap_callback(result)
+    }
+
+    /// Runs `func` with a mutable reference to Rust data wrapped into `object` with
+    /// `Class::wrap_data()`.
+    ///
+    /// This function is a helper for callbacks which access wrapped data, see `with_data()`
+    /// for the exceptions it raises.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, VM};
+    /// # VM::init();
+    ///
+    /// struct Counter {
+    ///     count: i64,
+    /// }
+    ///
+    /// let counter = Class::new("Counter", None).wrap_data(Counter { count: 1 });
+    ///
+    /// let count = VM::with_data_mut(&counter, |counter: &mut Counter| {
+    ///     counter.count += 1;
+    ///     counter.count
+    /// });
+    ///
+    /// assert_eq!(count, 2);
+    /// ```
+    pub fn with_data_mut<O, T, F, R>(object: &O, func: F) -> R
+        where O: Object,
+              T: Any,
+              F: FnOnce(&mut T) -> R
+    {
+        let value = object.value();
+
+        let result = match typed_data::get::<T>(&value) {
+            Some(data) => {
+                match data.try_borrow_mut() {
+                    Ok(mut data) => Self::protect_callback(|| func(&mut data)),
+                    Err(_) => Err(CallbackError::Borrowed),
+                }
+            }
+            None => Err(CallbackError::WrongType),
+        };
+
+        Self::unwrap_callback(result)
+    }
+
+    // Runs `func` catching both Ruby jumps and Rust panics, so the caller can release
+    // the borrowed data before they are raised again by `unwrap_callback()`
+    fn protect_callback<F, R>(func: F) -> Result<R, CallbackError>
+        where F: FnOnce() -> R
+    {
+        let mut result = None;
+
+        let protected = vm::protect(|| {
+            result = Some(panic::catch_unwind(AssertUnwindSafe(func)));
+        });
+
+        match result {
+            Some(Ok(result)) => Ok(result),
+            Some(Err(payload)) => Err(CallbackError::Panic(payload)),
+            // `result` is not set only if `func` was interrupted by a jump
+            None => Err(CallbackError::Jump(protected.err().unwrap_or(0))),
+        }
+    }
+
+    fn unwrap_callback<R>(result: Result<R, CallbackError>) -> R {
+        let exception = match result {
+            Ok(result) => return result,
+            Err(CallbackError::Jump(state)) => vm::jump_tag(state),
+            Err(CallbackError::Panic(payload)) => Self::panic_exception(payload),
+            Err(CallbackError::Borrowed) => {
+                Exception::new(&Class::from_existing("RuntimeError"),
+                               "Rust data is already borrowed by a running callback")
+            }
+            Err(CallbackError::WrongType) => {
+                Exception::new(&Class::from_existing("TypeError"),
+                               "Rust data has another type")
+            }
+        };
+
+        exception.raise()
+    }
+
+    /// Returns the value of a global variable.
+    ///
+    /// Special variables like `$!`, `$0` or `$PROGRAM_NAME` can be read in the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Class, Object, VM};
+    /// # VM::init();
+    ///
+    /// let stdout = VM::global_get("$stdout");
+    ///
+    /// assert_eq!(stdout.class(), Class::from_existing("IO"));
+    /// assert!(VM::global_get("$!").is_nil());
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// $stdout.class == IO
+    /// $!.nil? == true
+    /// ```
+    pub fn global_get(name: &str) -> AnyObject {
+        AnyObject::from(global::global_get(name))
+    }
+
+    /// Sets the value of a global variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Boolean, Object, VM};
+    /// # VM::init();
+    ///
+    /// VM::global_set("$VERBOSE", Boolean::new(true));
+    ///
+    /// assert_eq!(VM::global_get("$VERBOSE").try_convert_to::<Boolean>(), Ok(Boolean::new(true)));
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// $VERBOSE = true
+    ///
+    /// $VERBOSE == true
+    /// ```
+    pub fn global_set<T: Object>(name: &str, value: T) {
+        global::global_set(name, value.value());
+    }
+
+    /// Defines a global variable which calls `getter` when it is read and `setter` when
+    /// it is assigned.
+    ///
+    /// The closures live as long as the VM. GC does not see Ruby objects captured by them,
+    /// so they must be wrapped in `BoxedValue` to be kept alive.
+    ///
+    /// Reading or assigning the variable from its own closures and panics in the closures
+    /// raise a `RuntimeError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Object, VM};
+    /// # VM::init();
+    ///
+    /// let mut counter = 0;
+    ///
+    /// VM::define_virtual_variable("$counter",
+    ///                             move || {
+    ///                                 counter += 1;
+    ///                                 Fixnum::new(counter).to_any_object()
+    ///                             },
+    ///                             |value| println!("Assigned {:?}", value));
+    ///
+    /// assert_eq!(VM::global_get("$counter").try_convert_to::<Fixnum>(), Ok(Fixnum::new(1)));
+    /// assert_eq!(VM::global_get("$counter").try_convert_to::<Fixnum>(), Ok(Fixnum::new(2)));
+    /// ```
+    pub fn define_virtual_variable<G, S>(name: &str, getter: G, setter: S)
+        where G: FnMut() -> AnyObject + 'static,
+              S: FnMut(AnyObject) + 'static
+    {
+        let variable = VirtualVariable {
+            getter: Box::new(getter),
+            setter: Some(Box::new(setter)),
+        };
+
+        Self::define_hooked_variable(name, variable);
+    }
+
+    /// Defines a read-only global variable which calls `getter` when it is read.
+    ///
+    /// Assigning the variable raises a `NameError`. Ruby objects captured by `getter` must be
+    /// wrapped in `BoxedValue`, see `VM::define_virtual_variable()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Fixnum, Hash, Object, Symbol, VM};
+    /// # VM::init();
+    ///
+    /// VM::define_readonly_variable("$NATIVE_STATS", || {
+    ///     let mut stats = Hash::new();
+    ///     stats.store(Symbol::new("allocations"), Fixnum::new(42));
+    ///
+    ///     stats.to_any_object()
+    /// });
+    ///
+    /// let stats = VM::global_get("$NATIVE_STATS").try_convert_to::<Hash>().unwrap();
+    ///
+    /// assert_eq!(stats.at(Symbol::new("allocations")).try_convert_to::<Fixnum>(),
+    ///            Ok(Fixnum::new(42)));
+    /// ```
+    pub fn define_readonly_variable<G>(name: &str, getter: G)
+        where G: FnMut() -> AnyObject + 'static
+    {
+        let variable = VirtualVariable {
+            getter: Box::new(getter),
+            setter: None,
+        };
+
+        Self::define_hooked_variable(name, variable);
+    }
+
+    fn define_hooked_variable(name: &str, variable: VirtualVariable) {
+        let setter = if variable.setter.is_some() {
+            Some(virtual_variable_set as VariableSetter)
+        } else {
+            None
+        };
+
+        let data = typed_data::wrap(Value::from(0), variable);
+
+        global::define_hooked_variable(name, data, virtual_variable_get, setter);
+    }
+
+    /// Converts a block given to current method to a `Proc`
+    ///
+    /// It works similarly to `def method(&block)` which converts block to `Proc`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate ruru;
+    ///
+    /// use ruru::{Class, Object, Proc, RString, VM};
+    ///
+    /// class!(Greeter);
+    ///
+    /// methods!(
+    ///     Greeter,
+    ///     itself,
+    ///
+    ///     fn greet_rust_with() -> RString {
+    ///         let greeting_template = VM::block_proc();
+    ///         let name = RString::new("Rust").to_any_object();
+    ///
+    ///         greeting_template.call(vec![name]).try_convert_to::<RString>().unwrap()
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     Class::new("Greeter", None).define(|itself| {
+    ///         itself.def_self("greet_rust_with", greet_rust_with);
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// class Greeter
+    ///   def self.greet_rust_with(greeting_template)
+    ///     greeting_template.call('Rust')
+    ///   end
+    /// end
+    ///
+    /// Greeter.greet_rust_with do |name|
+    ///   "Hello, #{name}!"
+    /// end
+    /// # => "Hello, Rust!"
+    /// ```
+    pub fn block_proc() -> Proc {
+        Proc::from(vm::block_proc())
+    }
+
+    /// Checks if a block is given to current method.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate ruru;
+    ///
+    /// use ruru::{Class, Fixnum, Object, VM};
+    ///
+    /// class!(Calculator);
+    ///
+    /// methods!(
+    ///     Calculator,
+    ///     itself,
     ///
-    /// ```no_run
-    /// use ruru::VM;
-    /// # VM::init();
+    ///     fn calculate(number: Fixnum) -> Fixnum {
+    ///         let number = number.unwrap();
     ///
-    /// VM::require("some_ruby_file");
+    ///         if VM::block_given() {
+    ///             let result = VM::block_proc().call(vec![number.to_any_object()]);
+    ///
+    ///             result.try_convert_to::<Fixnum>().unwrap()
+    ///         } else {
+    ///             number
+    ///         }
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     Class::new("Calculator", None).define(|itself| {
+    ///         itself.def("calculate", calculate);
+    ///     });
+    /// }
     /// ```
     ///
     /// Ruby:
     ///
     /// ```ruby
-    /// require 'some_ruby_file'
+    /// class Calculator
+    ///   def calculate(number)
+    ///     if block_given?
+    ///       yield number
+    ///     else
+    ///       number
+    ///     end
+    ///   end
+    /// end
     /// ```
-    pub fn require(name: &str) {
-        vm::require(name);
+    pub fn block_given() -> bool {
+        vm::is_block_given()
     }
 
-    /// Raises an exception.
+    /// Yields an object to the block given to current method.
     ///
-    /// # Examples
+    /// Returns the value returned by the block.
     ///
-    /// ### Built-in exceptions
+    /// Unlike `block_proc()` and `Proc::call()`, yielding does not create a `Proc` object and
+    /// keeps the semantics of `break` and `next` in the block.
+    ///
+    /// **Warning!** `break`, `throw` and exceptions in the block leave the method with a
+    /// `longjmp` through Rust frames, destructors of Rust values which are alive in the method
+    /// (for example, a `Vec` or a `String`) are not run. Keep only Ruby objects and `Copy`
+    /// values alive across a yield, or drop owned values before it.
+    ///
+    /// # Examples
     ///
     /// ```no_run
-    /// use ruru::{Class, VM};
-    /// # VM::init();
+    /// #[macro_use]
+    /// extern crate ruru;
     ///
-    /// VM::raise(Class::from_existing("ArgumentError"), "Wrong argument");
-    /// ```
+    /// use ruru::{AnyObject, Class, Fixnum, Object, VM};
     ///
-    /// Ruby:
+    /// class!(Countdown);
     ///
-    /// ```ruby
-    /// raise ArgumentError, 'Wrong argument'
-    /// ```
+    /// methods!(
+    ///     Countdown,
+    ///     itself,
     ///
-    /// ### Custom exceptions
+    ///     fn each() -> AnyObject {
+    ///         if !VM::block_given() {
+    ///             return VM::enumerator(&itself, vec![]);
+    ///         }
     ///
-    /// ```no_run
-    /// use ruru::{Class, VM};
-    /// # VM::init();
+    ///         for number in (1..4).rev() {
+    ///             VM::yield_value(Fixnum::new(number));
+    ///         }
     ///
-    /// let standard_error = Class::from_existing("StandardError");
-    /// let custom_exception = Class::new("CustomException", Some(&standard_error));
+    ///         itself.to_any_object()
+    ///     }
+    /// );
     ///
-    /// VM::raise(custom_exception, "Something went wrong");
+    /// fn main() {
+    ///     Class::new("Countdown", None).define(|itself| {
+    ///         itself.send("include", vec![Class::from_existing("Enumerable").to_any_object()]);
+    ///         itself.def("each", each);
+    ///     });
+    /// }
     /// ```
     ///
     /// Ruby:
     ///
     /// ```ruby
-    /// class CustomException < StandardError
+    /// class Countdown
+    ///   include Enumerable
+    ///
+    ///   def each
+    ///     return to_enum(:each) unless block_given?
+    ///
+    ///     3.downto(1) { |number| yield number }
+    ///
+    ///     self
+    ///   end
     /// end
     ///
-    /// raise CustomException, 'Something went wrong'
+    /// Countdown.new.each { |number| puts number }
+    /// Countdown.new.map { |number| number * 2 } # => [6, 4, 2]
+    /// Countdown.new.each.next # => 3
     /// ```
-    pub fn raise(exception: Class, message: &str) {
-        vm::raise(exception.value(), message);
+    pub fn yield_value<T: Object>(object: T) -> AnyObject {
+        let result = vm::yield_value(object.value());
+
+        AnyObject::from(result)
     }
 
-    /// Converts a block given to current method to a `Proc`
+    /// Yields several objects to the block given to current method.
     ///
-    /// It works similarly to `def method(&block)` which converts block to `Proc`
+    /// Returns the value returned by the block.
+    ///
+    /// `break`, `throw` and exceptions in the block skip destructors of Rust values, see
+    /// `VM::yield_value()`.
     ///
     /// # Examples
     ///
@@ -112,25 +808,31 @@ impl VM {
     /// #[macro_use]
     /// extern crate ruru;
     ///
-    /// use ruru::{Class, Object, Proc, RString, VM};
+    /// use ruru::{AnyObject, Class, Fixnum, Object, RString, VM};
     ///
-    /// class!(Greeter);
+    /// class!(Registry);
     ///
     /// methods!(
-    ///     Greeter,
+    ///     Registry,
     ///     itself,
     ///
-    ///     fn greet_rust_with() -> RString {
-    ///         let greeting_template = VM::block_proc();
-    ///         let name = RString::new("Rust").to_any_object();
+    ///     fn each_pair() -> AnyObject {
+    ///         if !VM::block_given() {
+    ///             return VM::enumerator(&itself, vec![]);
+    ///         }
     ///
-    ///         greeting_template.call(vec![name]).try_convert_to::<RString>().unwrap()
+    ///         let key = RString::new("answer").to_any_object();
+    ///         let value = Fixnum::new(42).to_any_object();
+    ///
+    ///         VM::yield_values(vec![key, value]);
+    ///
+    ///         itself.to_any_object()
     ///     }
     /// );
     ///
     /// fn main() {
-    ///     Class::new("Greeter", None).define(|itself| {
-    ///         itself.def_self("greet_rust_with", greet_rust_with);
+    ///     Class::new("Registry", None).define(|itself| {
+    ///         itself.def("each_pair", each_pair);
     ///     });
     /// }
     /// ```
@@ -138,19 +840,39 @@ impl VM {
     /// Ruby:
     ///
     /// ```ruby
-    /// class Greeter
-    ///   def self.greet_rust_with(greeting_template)
-    ///     greeting_template.call('Rust')
-    ///   end
-    /// end
+    /// class Registry
+    ///   def each_pair
+    ///     return to_enum(:each_pair) unless block_given?
     ///
-    /// Greeter.greet_rust_with do |name|
-    ///   "Hello, #{name}!"
+    ///     yield 'answer', 42
+    ///
+    ///     self
+    ///   end
     /// end
-    /// # => "Hello, Rust!"
     /// ```
-    pub fn block_proc() -> Proc {
-        Proc::from(vm::block_proc())
+    pub fn yield_values(arguments: Vec<AnyObject>) -> AnyObject {
+        let (argc, argv) = util::create_arguments(arguments);
+        let result = vm::yield_values(argc, argv.as_ptr());
+
+        AnyObject::from(result)
+    }
+
+    /// Creates an `Enumerator` for current method called on `receiver` with `arguments`.
+    ///
+    /// It is usually returned from iterator methods when no block is given, similarly to Ruby
+    /// `return to_enum(__method__, *args) unless block_given?` (`RETURN_ENUMERATOR` in C API).
+    ///
+    /// This function must be called from a method callback.
+    ///
+    /// The method is called again by the enumerator with a block, so `break` in the block
+    /// skips destructors of Rust values alive in the method, see `VM::yield_value()`.
+    ///
+    /// See `VM::yield_value()` for examples.
+    pub fn enumerator<T: Object>(receiver: &T, arguments: Vec<AnyObject>) -> AnyObject {
+        let (argc, argv) = util::create_arguments(arguments);
+        let result = vm::enumerator_for_current_method(receiver.value(), argc, argv.as_ptr());
+
+        AnyObject::from(result)
     }
 
     // TODO: Move to other struct
@@ -166,7 +888,6 @@ impl VM {
     /// use ruru::types::Argc;
     /// use ruru::{AnyObject, Boolean, Class, Object, RString, VM};
     ///
-    /// #[no_mangle]
     /// pub extern fn string_eq(argc: Argc, argv: *const AnyObject, itself: RString) -> Boolean {
     ///     let argv = VM::parse_arguments(argc, argv);
     ///     let other_string = argv[0].try_convert_to::<RString>().unwrap();
@@ -182,6 +903,51 @@ impl VM {
         unsafe { slice::from_raw_parts(arguments, argc as usize).to_vec() }
     }
 
+    /// Removes keyword arguments from the arguments of a method.
+    ///
+    /// This function is a helper for callbacks created with `methods!` macro, do not use it
+    /// directly.
+    ///
+    /// Keywords are taken from the last argument only if the method was called with keywords
+    /// (`rb_keyword_given_p()`). A `Hash` passed as the last positional argument stays in
+    /// `arguments`.
+    pub fn take_keywords(arguments: &mut Vec<AnyObject>) -> Option<AnyObject> {
+        if vm::is_keyword_given() {
+            arguments.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Extracts values of keyword arguments returned by `take_keywords()`.
+    ///
+    /// This function is a helper for callbacks created with `methods!` macro, do not use it
+    /// directly.
+    ///
+    /// Returns values for `required` and then for `optional` keywords in the order they are
+    /// given, `None` stands for a keyword which was not passed.
+    ///
+    /// Raises an `ArgumentError` if a required keyword is missing or an unknown keyword is
+    /// passed, so values for `required` keywords are always `Some`.
+    pub fn parse_keywords(keywords: Option<AnyObject>,
+                          required: &[&str],
+                          optional: &[&str])
+                          -> Vec<Option<AnyObject>> {
+        let keyword_hash = match keywords {
+            Some(keywords) => hash::dup(keywords.value()),
+            None => Value::from(RubySpecialConsts::Nil as InternalValue),
+        };
+
+        vm::get_keywords(keyword_hash, required, optional)
+            .into_iter()
+            .map(|value| if value.is_undef() {
+                None
+            } else {
+                Some(AnyObject::from(value))
+            })
+            .collect()
+    }
+
     /// Release GVL for current thread.
     ///
     /// **Warning!** Due to MRI limitations, interaction with Ruby objects is not allowed while
@@ -191,6 +957,10 @@ impl VM {
     /// You should extract all the information from Ruby world before invoking
     /// `thread_call_without_gvl`.
     ///
+    /// The closures and the result must be `Send`, so Ruby objects cannot be captured by the
+    /// closures or returned from them. `func` receives a `NoGvl` token, pass it to
+    /// `thread_call_with_gvl` to interact with Ruby while the GVL is released.
+    ///
     /// GVL will be re-acquired when the closure is finished.
     ///
     /// # Examples
@@ -198,7 +968,7 @@ impl VM {
     /// ```no_run
     /// #[macro_use] extern crate ruru;
     ///
-    /// use ruru::{Class, Fixnum, Object, VM};
+    /// use ruru::{Class, Fixnum, NoGvl, Object, VM};
     ///
     /// class!(Calculator);
     ///
@@ -207,7 +977,7 @@ impl VM {
     ///     itself,
     ///
     ///     fn heavy_computation() -> Fixnum {
-    ///         let computation = || { 2 * 2 };
+    ///         let computation = |_no_gvl: &NoGvl| { 2 * 2 };
     ///         let unblocking_function = || {};
     ///
     ///         // release GVL for current thread until `computation` is completed
@@ -228,21 +998,289 @@ impl VM {
     /// }
     /// ```
     pub fn thread_call_without_gvl<F, R, G>(func: F, unblock_func: Option<G>) -> R
-        where F: FnOnce() -> R,
-              G: FnOnce()
+        where F: FnOnce(&NoGvl) -> R + Send,
+              R: Send,
+              G: FnOnce() + Send
     {
-        vm::thread_call_without_gvl(func, unblock_func)
+        vm::thread_call_without_gvl(|| func(&unsafe { NoGvl::assume() }), unblock_func)
     }
 
     pub fn thread_call_without_gvl2<F, R, G>(func: F, unblock_func: Option<G>) -> R
-        where F: FnOnce() -> R,
-              G: FnOnce()
+        where F: FnOnce(&NoGvl) -> R + Send,
+              R: Send,
+              G: FnOnce() + Send
     {
-        vm::thread_call_without_gvl2(func, unblock_func)
+        vm::thread_call_without_gvl2(|| func(&unsafe { NoGvl::assume() }), unblock_func)
     }
 
-    pub fn thread_call_with_gvl<F, R>(func: F) -> R
-        where F: FnOnce() -> R
+    /// Releases GVL for current thread and passes a `NoGvl` and a `CancellationToken` to `func`.
+    ///
+    /// The token is cancelled when Ruby interrupts the thread (for example, by `Thread#kill` or
+    /// `Timeout.timeout`). `func` should check the token regularly and return as soon as it is
+    /// cancelled. Pending interrupts are handled when the GVL is re-acquired, so the thread is
+    /// killed or the exception is raised right after `func` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Fixnum, Object, VM};
+    ///
+    /// class!(Calculator);
+    ///
+    /// methods!(
+    ///     Calculator,
+    ///     itself,
+    ///
+    ///     fn heavy_computation() -> Fixnum {
+    ///         let result = VM::thread_call_without_gvl_cancellable(|_no_gvl, token| {
+    ///             let mut sum = 0;
+    ///
+    ///             for number in 0..1_000_000_000 {
+    ///                 if number % 1_000 == 0 && token.is_cancelled() {
+    ///                     break;
+    ///                 }
+    ///
+    ///                 sum += number % 7;
+    ///             }
+    ///
+    ///             sum
+    ///         });
+    ///
+    ///         Fixnum::new(result)
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     Class::new("Calculator", None).define(|itself| {
+    ///         itself.def("heavy_computation", heavy_computation);
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// require 'timeout'
+    ///
+    /// # Raises `Timeout::Error` after one second instead of waiting for the computation
+    /// Timeout.timeout(1) { Calculator.new.heavy_computation }
+    /// ```
+    pub fn thread_call_without_gvl_cancellable<F, R>(func: F) -> R
+        where F: FnOnce(&NoGvl, &CancellationToken) -> R + Send,
+              R: Send
+    {
+        let token = CancellationToken::new();
+        let worker_token = token.clone();
+        let worker = move || func(&unsafe { NoGvl::assume() }, &worker_token);
+
+        vm::thread_call_without_gvl_with_ubf(worker,
+                                             cancel_token as CallbackPtr,
+                                             &token as *const CancellationToken as *const c_void)
+    }
+
+    /// Handles pending interrupts of the current thread (`Thread#kill`, `Thread#raise`,
+    /// signals etc).
+    ///
+    /// Long-running native code should call it regularly to stay responsive. If the thread
+    /// is interrupted, the exception is raised from this function.
+    pub fn check_interrupts() {
+        thread::check_ints();
+    }
+
+    /// Runs a future to completion with GVL released for current thread.
+    ///
+    /// The future is polled by a small built-in executor on current thread, no async runtime
+    /// is required. The thread sleeps while the future is pending until its waker is called,
+    /// other Ruby threads keep running in the meantime.
+    ///
+    /// If Ruby interrupts the thread (for example, by `Thread#kill` or `Timeout.timeout`),
+    /// the future is dropped and the interrupt is handled when the GVL is re-acquired.
+    /// `None` is returned if the interrupt does not raise an exception.
+    ///
+    /// The future and its output must be `Send`, so they cannot contain Ruby objects.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run,edition2018
+    /// #[macro_use] extern crate ruru;
+    ///
+    /// use ruru::{Class, Object, RString, VM};
+    ///
+    /// async fn fetch_greeting(name: String) -> String {
+    ///     format!("Hello, {}!", name)
+    /// }
+    ///
+    /// class!(Client);
+    ///
+    /// methods!(
+    ///     Client,
+    ///     itself,
+    ///
+    ///     fn greeting(name: RString) -> RString {
+    ///         let name = name.unwrap().to_string();
+    ///         let greeting = VM::block_on(fetch_greeting(name)).unwrap();
+    ///
+    ///         RString::new(&greeting)
+    ///     }
+    /// );
+    ///
+    /// fn main() {
+    ///     Class::new("Client", None).define(|itself| {
+    ///         itself.def("greeting", greeting);
+    ///     });
+    /// }
+    /// ```
+    pub fn block_on<F>(future: F) -> Option<F::Output>
+        where F: Future + Send,
+              F::Output: Send
+    {
+        executor::block_on(future)
+    }
+
+    /// Runs the garbage collector (Ruby `GC.start`).
+    pub fn gc_start() {
+        gc::start();
+    }
+
+    /// Disables the garbage collector until the returned guard is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{RString, VM};
+    /// # VM::init();
+    ///
+    /// {
+    ///     let _gc_disabled = VM::gc_disable();
+    ///
+    ///     // Objects are not collected while the guard is alive
+    ///     RString::new("ruru");
+    /// }
+    ///
+    /// // GC is enabled again
+    /// VM::gc_start();
+    /// ```
+    ///
+    /// Ruby:
+    ///
+    /// ```ruby
+    /// GC.disable
+    ///
+    /// 'ruru'
+    ///
+    /// GC.enable
+    /// GC.start
+    /// ```
+    #[must_use = "GC is enabled again as soon as the guard is dropped"]
+    pub fn gc_disable() -> GcDisableGuard {
+        GcDisableGuard::new()
+    }
+
+    /// Enables the garbage collector.
+    ///
+    /// Returns `true` if GC was disabled before.
+    pub fn gc_enable() -> bool {
+        gc::enable()
+    }
+
+    /// Marks an object as permanently reachable, so it is never garbage collected.
+    ///
+    /// Use it for objects stored in Rust statics (for example, cached classes or frozen
+    /// strings) which are not referenced from Ruby.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let separator = RString::new(", ");
+    ///
+    /// VM::gc_register_mark_object(&separator);
+    /// ```
+    pub fn gc_register_mark_object<T: Object>(object: &T) {
+        gc::register_mark_object(object.value());
+    }
+
+    /// Registers a memory location which holds a `Value` as a GC root.
+    ///
+    /// The object stored at `address` at the moment of each garbage collection is kept alive,
+    /// so the location can be updated after registration.
+    ///
+    /// `BoxedValue` is a safe alternative for objects held by Rust data structures.
+    ///
+    /// # Safety
+    ///
+    /// `address` must stay valid until it is unregistered with `gc_unregister_address()`
+    /// (it should usually point to a `static`).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::ptr;
+    ///
+    /// use ruru::types::Value;
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// // `false` until a message is stored
+    /// static mut LAST_MESSAGE: Value = Value { value: 0 };
+    ///
+    /// unsafe {
+    ///     VM::gc_register_address(ptr::addr_of_mut!(LAST_MESSAGE));
+    ///
+    ///     LAST_MESSAGE = RString::new("Hello").value();
+    /// }
+    /// ```
+    pub unsafe fn gc_register_address(address: *mut Value) {
+        gc::register_address(address);
+    }
+
+    /// Unregisters a memory location registered with `gc_register_address()`.
+    ///
+    /// # Safety
+    ///
+    /// `address` must have been registered with `gc_register_address()`.
+    pub unsafe fn gc_unregister_address(address: *mut Value) {
+        gc::unregister_address(address);
+    }
+
+    /// Makes sure that `object` is kept on the stack (and is not garbage collected) until
+    /// this point, like `RB_GC_GUARD` macro in C.
+    ///
+    /// The compiler may drop a local variable as soon as its last use is passed, while
+    /// the object may still be in use by Ruby (for example, when only a pointer to the data
+    /// of a string is used). Call `gc_guard()` after the last such use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ruru::{Object, RString, VM};
+    /// # VM::init();
+    ///
+    /// let string = RString::new("ruru");
+    /// let value = string.value();
+    ///
+    /// // ... pass `value` to C functions of MRI ...
+    ///
+    /// VM::gc_guard(&string);
+    /// ```
+    pub fn gc_guard<T: Object>(object: &T) {
+        // A volatile read can not be optimized away, so the object stays reachable until here
+        unsafe {
+            ptr::read_volatile(&object.value());
+        }
+    }
+
+    /// Re-acquires GVL inside of a closure passed to `thread_call_without_gvl`.
+    ///
+    /// `no_gvl` is the token received by that closure. Ruby objects cannot be returned from
+    /// `func`, because they must not be used after the GVL is released again.
+    pub fn thread_call_with_gvl<F, R>(_no_gvl: &NoGvl, func: F) -> R
+        where F: FnOnce() -> R,
+              R: Send
     {
         vm::thread_call_with_gvl(func)
     }
@@ -253,3 +1291,34 @@ impl VM {
         vm::protect(func)
     }
 }
+
+enum CallbackError {
+    Borrowed,
+    Jump(c_int),
+    Panic(Box<dyn Any + Send>),
+    WrongType,
+}
+
+struct VirtualVariable {
+    getter: Box<dyn FnMut() -> AnyObject>,
+    setter: Option<Box<dyn FnMut(AnyObject)>>,
+}
+
+// Both functions raise if the closures access the variable recursively or panic
+
+unsafe extern "C" fn virtual_variable_get(_id: Id, data: *mut Value) -> Value {
+    let variable = AnyObject::from(*data);
+
+    VM::with_data_mut(&variable,
+                      |variable: &mut VirtualVariable| (variable.getter)().value())
+}
+
+unsafe extern "C" fn virtual_variable_set(value: Value, _id: Id, data: *mut Value) {
+    let variable = AnyObject::from(*data);
+
+    VM::with_data_mut(&variable, |variable: &mut VirtualVariable| {
+        if let Some(ref mut setter) = variable.setter {
+            setter(AnyObject::from(value));
+        }
+    });
+}
@@ -62,13 +62,18 @@
 macro_rules! class {
     ($class: ident) => {
         #[derive(Debug, PartialEq)]
+        #[repr(transparent)]
         pub struct $class {
             value: $crate::types::Value,
+            _not_send: ::std::marker::PhantomData<*mut ()>,
         }
 
         impl From<$crate::types::Value> for $class {
             fn from(value: $crate::types::Value) -> Self {
-                $class { value: value }
+                $class {
+                    value: value,
+                    _not_send: ::std::marker::PhantomData,
+                }
             }
         }
 
@@ -153,7 +158,6 @@ macro_rules! unsafe_methods {
         )*
     ) => {
         $(
-            #[no_mangle]
             #[allow(unused_mut)]
             pub extern fn $method_name(argc: $crate::types::Argc,
                                        argv: *const $crate::AnyObject,
@@ -188,10 +192,41 @@ macro_rules! unsafe_methods {
 /// For example, if you declare `number: Fixnum` in the method definition, it will have actual
 /// type `number: Result<Fixnum, Error>`.
 ///
+/// Arguments can also be declared as optional:
+///
+///  - `number: Option<Fixnum>` has type `Result<Option<Fixnum>, Error>` and is `Ok(None)` when
+///     the argument is not passed;
+///  - `number: Fixnum = Fixnum::new(1)` has type `Result<Fixnum, Error>` and is set to the
+///     default value when the argument is not passed.
+///
+/// If a method receives more arguments than declared, an `ArgumentError` is raised
+/// (for example, `wrong number of arguments (given 3, expected 1..2)`).
+///
+/// A splat argument `*rest: Array` takes all the remaining positional arguments (it must be the
+/// last positional argument, `Vec<AnyObject>` can be used instead of `Array`). A method which has
+/// a splat argument accepts any number of arguments.
+///
+/// A block argument `&block: Option<Proc>` is `Some(Proc)` when a block is given to the method
+/// and `None` otherwise.
+///
+/// Keyword arguments are declared with `key` prefix and support the same forms:
+/// `key port: Fixnum` (required), `key port: Option<Fixnum>` and
+/// `key port: Fixnum = Fixnum::new(8080)`. Keywords are taken only from keyword arguments of the
+/// call, a `Hash` passed as the last positional argument is not treated as keywords. An
+/// `ArgumentError` is raised when a required keyword is missing or an unknown keyword is passed.
+///
+/// Callbacks are ordinary Rust functions with module-scoped symbols, so callbacks with the same
+/// name can be defined in different modules of a crate.
+///
+/// Methods meant for internal use can be registered with `Object::def_private()` or
+/// `Object::def_protected()` to keep them out of the public API of the class.
+///
 /// See examples below and docs for `Object::try_convert_to()` for more information.
 ///
 /// # Examples
 ///
+/// ### Safe conversions
+///
 /// To launch a server in Rust, you plan to write a simple `Server` class
 ///
 /// ```ruby
@@ -270,6 +305,196 @@ macro_rules! unsafe_methods {
 ///   end
 /// end
 /// ```
+///
+/// ### Optional arguments
+///
+/// ```
+/// #[macro_use]
+/// extern crate ruru;
+///
+/// use ruru::{Class, Fixnum, Object, RString, VM};
+///
+/// class!(Greeter);
+///
+/// methods!(
+///     Greeter,
+///     itself,
+///
+///     fn greet(
+///         name: RString,
+///         times: Fixnum = Fixnum::new(1),
+///         suffix: Option<RString>
+///     ) -> RString {
+///         let name = name.map(|name| name.to_string()).unwrap_or("stranger".to_string());
+///         let times = times.map(|times| times.to_i64()).unwrap_or(1) as usize;
+///
+///         let suffix = match suffix {
+///             Ok(Some(suffix)) => suffix.to_string(),
+///             _ => "!".to_string(),
+///         };
+///
+///         let greeting = format!("Hello, {}{}", name, suffix);
+///
+///         RString::new(&vec![greeting; times].join(" "))
+///     }
+/// );
+///
+/// fn main() {
+///     # VM::init();
+///     let greeter = Class::new("Greeter", None).define(|itself| {
+///         itself.def("greet", greet);
+///     }).new_instance(vec![]);
+///
+///     let name = RString::new("Rust").to_any_object();
+///     let times = Fixnum::new(2).to_any_object();
+///
+///     let greeting = greeter.send("greet", vec![name, times]).try_convert_to::<RString>();
+///
+///     assert_eq!(greeting.unwrap().to_string(), "Hello, Rust! Hello, Rust!".to_string());
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// class Greeter
+///   def greet(name, times = 1, suffix = nil)
+///     suffix ||= '!'
+///
+///     (["Hello, #{name}#{suffix}"] * times).join(' ')
+///   end
+/// end
+///
+/// Greeter.new.greet('Rust', 2) # => "Hello, Rust! Hello, Rust!"
+/// Greeter.new.greet('Rust', 2, '?', 3) # => ArgumentError
+/// ```
+///
+/// ### Keyword arguments
+///
+/// ```
+/// #[macro_use]
+/// extern crate ruru;
+///
+/// use ruru::{Boolean, Class, Fixnum, Hash, Object, RString, Symbol, VM};
+///
+/// class!(Connection);
+///
+/// methods!(
+///     Connection,
+///     itself,
+///
+///     fn connect(
+///         host: RString,
+///         key port: Fixnum = Fixnum::new(80),
+///         key secure: Option<Boolean>
+///     ) -> RString {
+///         let host = host.map(|host| host.to_string()).unwrap_or("localhost".to_string());
+///         let port = port.map(|port| port.to_i64()).unwrap_or(80);
+///
+///         let scheme = match secure {
+///             Ok(Some(ref secure)) if secure.to_bool() => "https",
+///             _ => "http",
+///         };
+///
+///         RString::new(&format!("{}://{}:{}", scheme, host, port))
+///     }
+/// );
+///
+/// fn main() {
+///     # VM::init();
+///     let connection = Class::new("Connection", None).define(|itself| {
+///         itself.def("connect", connect);
+///     }).new_instance(vec![]);
+///
+///     let mut keywords = Hash::new();
+///
+///     keywords.store(Symbol::new("port"), Fixnum::new(8443));
+///     keywords.store(Symbol::new("secure"), Boolean::new(true));
+///
+///     let host = RString::new("example.com").to_any_object();
+///     let url = connection.send_with_kwargs("connect", vec![host], keywords);
+///
+///     assert_eq!(url.try_convert_to::<RString>().unwrap().to_string(),
+///                "https://example.com:8443".to_string());
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// class Connection
+///   def connect(host, port: 80, secure: nil)
+///     "#{secure ? 'https' : 'http'}://#{host}:#{port}"
+///   end
+/// end
+///
+/// Connection.new.connect('example.com', port: 8443, secure: true)
+/// # => "https://example.com:8443"
+///
+/// Connection.new.connect('example.com', timeout: 1) # => ArgumentError (unknown keyword)
+/// ```
+///
+/// ### Splat and block arguments
+///
+/// ```
+/// #[macro_use]
+/// extern crate ruru;
+///
+/// use ruru::{Array, Class, Fixnum, Object, Proc, VM};
+///
+/// class!(Collector);
+///
+/// methods!(
+///     Collector,
+///     itself,
+///
+///     fn collect(first: Fixnum, *rest: Array, &block: Option<Proc>) -> Array {
+///         let mut result = Array::new();
+///
+///         if let Ok(first) = first {
+///             result.push(first);
+///         }
+///
+///         for item in rest.into_iter() {
+///             let item = match block {
+///                 Some(ref block) => block.call(vec![item]),
+///                 None => item,
+///             };
+///
+///             result.push(item);
+///         }
+///
+///         result
+///     }
+/// );
+///
+/// fn main() {
+///     # VM::init();
+///     let collector = Class::new("Collector", None).define(|itself| {
+///         itself.def("collect", collect);
+///     }).new_instance(vec![]);
+///
+///     let arguments = (1..4).map(|number| Fixnum::new(number).to_any_object()).collect();
+///     let result = collector.send("collect", arguments).try_convert_to::<Array>().unwrap();
+///
+///     assert_eq!(result.length(), 3);
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// class Collector
+///   def collect(first, *rest, &block)
+///     rest = rest.map(&block) if block
+///
+///     [first, *rest]
+///   end
+/// end
+///
+/// Collector.new.collect(1, 2, 3) # => [1, 2, 3]
+/// Collector.new.collect(1, 2, 3) { |n| n * 10 } # => [1, 20, 30]
+/// ```
 #[macro_export]
 macro_rules! methods {
     (
@@ -277,41 +502,606 @@ macro_rules! methods {
         $itself_name: ident,
         $(
             fn $method_name: ident
-            ($($arg_name: ident: $arg_type: ty),*) -> $return_type: ident $body: block
+            ($($args: tt)*) -> $return_type: ident $body: block
         )*
     ) => {
         $(
-            #[no_mangle]
             #[allow(unused_mut)]
             pub extern fn $method_name(argc: $crate::types::Argc,
                                        argv: *const $crate::AnyObject,
                                        mut $itself_name: $itself_class) -> $return_type {
-                let _arguments = $crate::VM::parse_arguments(argc, argv);
-                let mut _i = 0;
-
-                $(
-                    let $arg_name =
-                        _arguments
-                            .get(_i)
-                            .ok_or({
-                                $crate::result::Error::ArgumentError(
-                                    format!(
-                                        "Argument '{}: {}' not found for method '{}'",
-                                        stringify!($arg_name),
-                                        stringify!($arg_type),
-                                        stringify!($method_name)
-                                    )
-                                )
-                            }).and_then(|argument| {
-                                <$crate::AnyObject as $crate::Object>
-                                    ::try_convert_to::<$arg_type>(argument)
-                            });
+                let mut _arguments = $crate::VM::parse_arguments(argc, argv);
 
-                    _i += 1;
-                )*
+                $crate::__ruru_arguments!(_arguments, $method_name; $($args)*,);
 
                 $body
             }
         )*
     }
 }
+
+/// Creates callbacks for Ruby methods with a fixed number of arguments
+///
+/// Unlike `methods!`, the callbacks receive each argument as a separate parameter (Ruby C API
+/// functions with arity from `0` to `15`), so no `Vec` of arguments is allocated for a call.
+/// Ruby checks the number of arguments itself, raises an `ArgumentError` when it is wrong,
+/// and `Method#arity` returns the exact number of arguments.
+///
+/// Optional, keyword, splat and block arguments are not supported, use `methods!` for them.
+///
+/// Similarly to `methods!`, each argument has type `Result<Object, Error>` which is `Err` when
+/// the argument cannot be converted to the declared type.
+///
+/// Each method becomes a function returning `FixedMethod`, which is registered with
+/// `Object::def_fixed()` or `Object::def_self_fixed()`. A method with more than 15 arguments
+/// does not compile:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate ruru;
+///
+/// use ruru::{AnyObject, NilClass};
+///
+/// class!(Wide);
+///
+/// fixed_methods!(
+///     Wide,
+///     itself,
+///
+///     fn wide(a: AnyObject, b: AnyObject, c: AnyObject, d: AnyObject, e: AnyObject,
+///             f: AnyObject, g: AnyObject, h: AnyObject, i: AnyObject, j: AnyObject,
+///             k: AnyObject, l: AnyObject, m: AnyObject, n: AnyObject, o: AnyObject,
+///             p: AnyObject) -> NilClass {
+///         NilClass::new()
+///     }
+/// );
+///
+/// fn main() {}
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate ruru;
+///
+/// use ruru::{Class, Fixnum, Object, VM};
+///
+/// class!(Rectangle);
+///
+/// fixed_methods!(
+///     Rectangle,
+///     itself,
+///
+///     fn rectangle_area(width: Fixnum, height: Fixnum) -> Fixnum {
+///         let width = width.map(|width| width.to_i64()).unwrap_or(0);
+///         let height = height.map(|height| height.to_i64()).unwrap_or(0);
+///
+///         Fixnum::new(width * height)
+///     }
+/// );
+///
+/// fn main() {
+///     # VM::init();
+///     Class::new("Rectangle", None).define(|itself| {
+///         itself.def_fixed("area", rectangle_area);
+///     });
+///
+///     let rectangle = Class::from_existing("Rectangle").new_instance(vec![]);
+///     let arguments = vec![Fixnum::new(2).to_any_object(), Fixnum::new(3).to_any_object()];
+///     let area = rectangle.send("area", arguments).try_convert_to::<Fixnum>();
+///
+///     assert_eq!(area, Ok(Fixnum::new(6)));
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// class Rectangle
+///   def area(width, height)
+///     width * height
+///   end
+/// end
+///
+/// Rectangle.new.area(2, 3) == 6
+/// ```
+#[macro_export]
+macro_rules! fixed_methods {
+    (
+        $itself_class: ty,
+        $itself_name: ident,
+        $(
+            fn $method_name: ident
+            ($($arg_name: ident: $arg_type: ty),*) -> $return_type: ty $body: block
+        )*
+    ) => {
+        $(
+            pub fn $method_name() -> $crate::types::FixedMethod {
+                const ARITY: $crate::types::Argc = $crate::__ruru_count!($($arg_name)*);
+                const _: () = assert!(ARITY <= 15, "fixed_methods! supports up to 15 arguments");
+
+                #[allow(unused_mut)]
+                extern fn function(mut $itself_name: $itself_class,
+                                   $($arg_name: $crate::AnyObject),*) -> $return_type {
+                    $(
+                        let $arg_name = <$crate::AnyObject as $crate::Object>
+                            ::try_convert_to::<$arg_type>(&$arg_name);
+                    )*
+
+                    $body
+                }
+
+                // `function` receives exactly `ARITY` arguments
+                unsafe {
+                    $crate::types::FixedMethod::new(ARITY, function as $crate::types::CallbackPtr)
+                }
+            }
+        )*
+    }
+}
+
+/// Declares the entry point of an extension
+///
+/// Ruby calls `Init_<name>()` function when an extension `<name>.so` (or `.bundle`) is required.
+/// This macro creates this function and runs the given code inside it.
+///
+/// The name of the extension defaults to the name of the crate. It can be given explicitly as
+/// the first argument if the compiled library is renamed.
+///
+/// The body is either a block or a list of `object => definition` pairs, where `definition` is
+/// a closure passed to `Object::define()` (see examples below).
+///
+/// Panics are converted to Ruby `RuntimeError`s, so they do not unwind into Ruby.
+///
+/// # Examples
+///
+/// ### A block
+///
+/// ```
+/// #[macro_use]
+/// extern crate ruru;
+///
+/// use ruru::{Boolean, Class, Object, RString};
+///
+/// methods!(
+///     RString,
+///     itself,
+///
+///     fn string_is_blank() -> Boolean {
+///         Boolean::new(itself.to_string().trim().is_empty())
+///     }
+/// );
+///
+/// // Defines `Init_my_extension()`, `require 'my_extension'` calls it
+/// init!(my_extension, {
+///     Class::from_existing("String").define(|itself| {
+///         itself.def("blank?", string_is_blank);
+///     });
+/// });
+/// # fn main() {}
+/// ```
+///
+/// ### Several classes
+///
+/// ```
+/// #[macro_use]
+/// extern crate ruru;
+///
+/// use ruru::{Class, Fixnum, Object, RString};
+///
+/// class!(Calculator);
+///
+/// methods!(
+///     Calculator,
+///     itself,
+///
+///     fn calculator_answer() -> Fixnum {
+///         Fixnum::new(42)
+///     }
+/// );
+///
+/// methods!(
+///     RString,
+///     itself,
+///
+///     fn string_shout() -> RString {
+///         RString::new(&itself.to_string().to_uppercase())
+///     }
+/// );
+///
+/// // Defines `Init_<crate name>()`
+/// init!(
+///     Class::new("Calculator", None) => |itself| {
+///         itself.def("answer", calculator_answer);
+///     },
+///     Class::from_existing("String") => |itself| {
+///         itself.def("shout", string_shout);
+///     }
+/// );
+/// # fn main() {}
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// class Calculator
+///   def answer
+///     42
+///   end
+/// end
+///
+/// class String
+///   def shout
+///     upcase
+///   end
+/// end
+/// ```
+#[macro_export]
+macro_rules! init {
+    ($name: ident, $body: block) => {
+        #[export_name = concat!("Init_", stringify!($name))]
+        pub extern "C" fn __ruru_init() {
+            $crate::VM::raise_on_panic(|| $body);
+        }
+    };
+
+    ($body: block) => {
+        #[export_name = concat!("Init_", env!("CARGO_CRATE_NAME"))]
+        pub extern "C" fn __ruru_init() {
+            $crate::VM::raise_on_panic(|| $body);
+        }
+    };
+
+    ($name: ident, $($object: expr => $definition: expr),+ $(,)*) => {
+        $crate::init!($name, {
+            $(
+                $crate::Object::define(&mut $object, $definition);
+            )+
+        });
+    };
+
+    ($($object: expr => $definition: expr),+ $(,)*) => {
+        $crate::init!({
+            $(
+                $crate::Object::define(&mut $object, $definition);
+            )+
+        });
+    };
+}
+
+/// Binds arguments of a method created with `methods!` macro
+///
+/// This macro is an implementation detail of `methods!`, do not use it directly.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ruru_arguments {
+    // Split declared arguments into positional, required keyword and optional keyword ones.
+    //
+    // Each argument is normalized to one of the following forms:
+    //
+    //  - `(name: Type)`
+    //  - `(name: Option<Type>)`
+    //  - `(name: Type = default)`
+    //  - `(*name: Type)`
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*] [$($required: tt)*] [$($optional: tt)*];
+        key $arg_name: ident: Option<$arg_type: ty>, $($rest: tt)*
+    ) => {
+        $crate::__ruru_arguments!(
+            @split $arguments, $method_name;
+            [$($positional)*] [$($required)*] [$($optional)* ($arg_name: Option<$arg_type>)];
+            $($rest)*
+        );
+    };
+
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*] [$($required: tt)*] [$($optional: tt)*];
+        key $arg_name: ident: $arg_type: ty = $default: expr, $($rest: tt)*
+    ) => {
+        $crate::__ruru_arguments!(
+            @split $arguments, $method_name;
+            [$($positional)*] [$($required)*] [$($optional)* ($arg_name: $arg_type = $default)];
+            $($rest)*
+        );
+    };
+
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*] [$($required: tt)*] [$($optional: tt)*];
+        key $arg_name: ident: $arg_type: ty, $($rest: tt)*
+    ) => {
+        $crate::__ruru_arguments!(
+            @split $arguments, $method_name;
+            [$($positional)*] [$($required)* ($arg_name: $arg_type)] [$($optional)*];
+            $($rest)*
+        );
+    };
+
+    // Block argument is bound right away: `&name: Option<Proc>`
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*] [$($required: tt)*] [$($optional: tt)*];
+        &$arg_name: ident: Option<$arg_type: ty>, $($rest: tt)*
+    ) => {
+        let $arg_name: Option<$arg_type> = if $crate::VM::block_given() {
+            Some($crate::VM::block_proc())
+        } else {
+            None
+        };
+
+        $crate::__ruru_arguments!(
+            @split $arguments, $method_name;
+            [$($positional)*] [$($required)*] [$($optional)*];
+            $($rest)*
+        );
+    };
+
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*] [$($required: tt)*] [$($optional: tt)*];
+        *$arg_name: ident: $arg_type: ty, $($rest: tt)*
+    ) => {
+        $crate::__ruru_arguments!(
+            @split $arguments, $method_name;
+            [$($positional)* (*$arg_name: $arg_type)] [$($required)*] [$($optional)*];
+            $($rest)*
+        );
+    };
+
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*] [$($required: tt)*] [$($optional: tt)*];
+        $arg_name: ident: Option<$arg_type: ty>, $($rest: tt)*
+    ) => {
+        $crate::__ruru_arguments!(
+            @split $arguments, $method_name;
+            [$($positional)* ($arg_name: Option<$arg_type>)] [$($required)*] [$($optional)*];
+            $($rest)*
+        );
+    };
+
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*] [$($required: tt)*] [$($optional: tt)*];
+        $arg_name: ident: $arg_type: ty = $default: expr, $($rest: tt)*
+    ) => {
+        $crate::__ruru_arguments!(
+            @split $arguments, $method_name;
+            [$($positional)* ($arg_name: $arg_type = $default)] [$($required)*] [$($optional)*];
+            $($rest)*
+        );
+    };
+
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*] [$($required: tt)*] [$($optional: tt)*];
+        $arg_name: ident: $arg_type: ty, $($rest: tt)*
+    ) => {
+        $crate::__ruru_arguments!(
+            @split $arguments, $method_name;
+            [$($positional)* ($arg_name: $arg_type)] [$($required)*] [$($optional)*];
+            $($rest)*
+        );
+    };
+
+    // All the arguments are split (only the trailing comma is left), no keyword arguments
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*] [] [];
+        $(,)*
+    ) => {
+        $crate::__ruru_arguments!(@positional $arguments, $method_name, 0, 0; $($positional)*);
+    };
+
+    // All the arguments are split, take keyword arguments before binding positional ones and
+    // parse them after the number of positional arguments is checked
+    (
+        @split $arguments: ident, $method_name: ident;
+        [$($positional: tt)*]
+        [$(($required_name: ident $($required: tt)*))*]
+        [$(($optional_name: ident $($optional: tt)*))*];
+        $(,)*
+    ) => {
+        let _keywords = $crate::VM::take_keywords(&mut $arguments);
+
+        $crate::__ruru_arguments!(@positional $arguments, $method_name, 0, 0; $($positional)*);
+
+        // `parse_keywords` raises on missing and unknown keywords, positional arguments are
+        // already bound, so the arguments are dropped before
+        ::std::mem::drop($arguments);
+
+        let _keywords = $crate::VM::parse_keywords(
+            _keywords,
+            &[$(stringify!($required_name)),*],
+            &[$(stringify!($optional_name)),*]
+        );
+
+        let mut _keywords = _keywords.into_iter();
+
+        $(
+            $crate::__ruru_arguments!(
+                @keyword _keywords, $method_name; $required_name $($required)*
+            );
+        )*
+
+        $(
+            $crate::__ruru_arguments!(
+                @keyword _keywords, $method_name; $optional_name $($optional)*
+            );
+        )*
+    };
+
+    // All the positional arguments are bound, check that no extra arguments were passed.
+    // `$required` is the position after the last required argument, because optional arguments
+    // before it cannot be omitted.
+    (@positional $arguments: ident, $method_name: ident, $index: expr, $required: expr;) => {
+        let _max = $index;
+
+        if $arguments.len() > _max {
+            // Raising does not run destructors of Rust values, so the exception is created
+            // and the owned values are dropped before
+            let _exception = {
+                let _required = $required;
+
+                let _expected = if _required == _max {
+                    _max.to_string()
+                } else {
+                    format!("{}..{}", _required, _max)
+                };
+
+                let _error = $crate::result::Error::ArgumentError(
+                    format!(
+                        "wrong number of arguments (given {}, expected {})",
+                        $arguments.len(),
+                        _expected
+                    )
+                );
+
+                $crate::Exception::new(&_error.to_exception(), &_error.to_string())
+            };
+
+            ::std::mem::drop($arguments);
+
+            _exception.raise();
+        }
+    };
+
+    // Splat argument: `*name: Array`, takes all the remaining arguments (no arity check)
+    (
+        @positional $arguments: ident, $method_name: ident, $index: expr, $required: expr;
+        (*$arg_name: ident: $arg_type: ty)
+    ) => {
+        let $arg_name: $arg_type = $arguments.iter().skip($index).cloned().collect();
+    };
+
+    // Optional argument: `name: Option<Type>`
+    (
+        @positional $arguments: ident, $method_name: ident, $index: expr, $required: expr;
+        ($arg_name: ident: Option<$arg_type: ty>) $($rest: tt)*
+    ) => {
+        let $arg_name = match $arguments.get($index) {
+            Some(argument) => {
+                <$crate::AnyObject as $crate::Object>
+                    ::try_convert_to::<$arg_type>(argument)
+                    .map(Some)
+            }
+            None => Ok(None),
+        };
+
+        $crate::__ruru_arguments!(
+            @positional $arguments, $method_name, $index + 1, $required;
+            $($rest)*
+        );
+    };
+
+    // Argument with default value: `name: Type = default`
+    (
+        @positional $arguments: ident, $method_name: ident, $index: expr, $required: expr;
+        ($arg_name: ident: $arg_type: ty = $default: expr) $($rest: tt)*
+    ) => {
+        let $arg_name = match $arguments.get($index) {
+            Some(argument) => {
+                <$crate::AnyObject as $crate::Object>
+                    ::try_convert_to::<$arg_type>(argument)
+            }
+            None => Ok($default),
+        };
+
+        $crate::__ruru_arguments!(
+            @positional $arguments, $method_name, $index + 1, $required;
+            $($rest)*
+        );
+    };
+
+    // Required argument: `name: Type`
+    (
+        @positional $arguments: ident, $method_name: ident, $index: expr, $required: expr;
+        ($arg_name: ident: $arg_type: ty) $($rest: tt)*
+    ) => {
+        let $arg_name =
+            $arguments
+                .get($index)
+                .ok_or({
+                    $crate::result::Error::ArgumentError(
+                        format!(
+                            "Argument '{}: {}' not found for method '{}'",
+                            stringify!($arg_name),
+                            stringify!($arg_type),
+                            stringify!($method_name)
+                        )
+                    )
+                }).and_then(|argument| {
+                    <$crate::AnyObject as $crate::Object>
+                        ::try_convert_to::<$arg_type>(argument)
+                });
+
+        $crate::__ruru_arguments!(
+            @positional $arguments, $method_name, $index + 1, $index + 1;
+            $($rest)*
+        );
+    };
+
+    // Optional keyword argument: `key name: Option<Type>`
+    (@keyword $keywords: ident, $method_name: ident; $arg_name: ident: Option<$arg_type: ty>) => {
+        let $arg_name = match $keywords.next() {
+            Some(Some(argument)) => {
+                <$crate::AnyObject as $crate::Object>
+                    ::try_convert_to::<$arg_type>(&argument)
+                    .map(Some)
+            }
+            _ => Ok(None),
+        };
+    };
+
+    // Keyword argument with default value: `key name: Type = default`
+    (
+        @keyword $keywords: ident, $method_name: ident;
+        $arg_name: ident: $arg_type: ty = $default: expr
+    ) => {
+        let $arg_name = match $keywords.next() {
+            Some(Some(argument)) => {
+                <$crate::AnyObject as $crate::Object>
+                    ::try_convert_to::<$arg_type>(&argument)
+            }
+            _ => Ok($default),
+        };
+    };
+
+    // Required keyword argument: `key name: Type`, missing ones are reported by `parse_keywords`
+    (@keyword $keywords: ident, $method_name: ident; $arg_name: ident: $arg_type: ty) => {
+        let $arg_name = match $keywords.next() {
+            Some(Some(argument)) => {
+                <$crate::AnyObject as $crate::Object>
+                    ::try_convert_to::<$arg_type>(&argument)
+            }
+            _ => {
+                Err($crate::result::Error::ArgumentError(
+                    format!(
+                        "Keyword argument '{}: {}' not found for method '{}'",
+                        stringify!($arg_name),
+                        stringify!($arg_type),
+                        stringify!($method_name)
+                    )
+                ))
+            }
+        };
+    };
+
+    // Entry point
+    ($arguments: ident, $method_name: ident; $($args: tt)*) => {
+        $crate::__ruru_arguments!(@split $arguments, $method_name; [] [] []; $($args)*);
+    };
+}
+
+/// Counts arguments of a method created with `fixed_methods!` macro
+///
+/// This macro is an implementation detail of `fixed_methods!`, do not use it directly.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ruru_count {
+    () => { 0 };
+    ($head: ident $($tail: ident)*) => { 1 + $crate::__ruru_count!($($tail)*) };
+}
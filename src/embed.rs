@@ -0,0 +1,177 @@
+//! Embedding Ruby into Rust applications
+//!
+//! `VM::init()` only starts the virtual machine. Applications which run Ruby code themselves
+//! (like background job processors or application servers) usually also need the standard
+//! `$LOAD_PATH`, a script name, interpreter options and a proper shutdown. `VmBuilder`
+//! configures all of them and returns a `VmGuard` which shuts the VM down when dropped.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ruru::embed::VmBuilder;
+//! use ruru::VM;
+//!
+//! fn main() {
+//!     let _vm = VmBuilder::new()
+//!         .script_name("worker")
+//!         .load_path("./lib")
+//!         .require("json")
+//!         .default_external("UTF-8")
+//!         .option("-W0")
+//!         .init()
+//!         .unwrap();
+//!
+//!     VM::eval("JSON.generate(answer: 42)").unwrap();
+//!
+//!     // `ruby_cleanup()` is called here when `_vm` is dropped
+//! }
+//! ```
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::marker::PhantomData;
+use std::result;
+
+use binding::vm;
+
+pub type Result<T> = result::Result<T, Error>;
+
+/// An error returned when the embedded Ruby VM can not be started
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The VM has already been started by `VmBuilder::init()` or `VM::init()`
+    AlreadyInitialized,
+    /// Interpreter options are invalid, contains the exit status reported by Ruby
+    InvalidOptions(i32),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Error::AlreadyInitialized => write!(f, "Ruby VM is already initialized"),
+            Error::InvalidOptions(status) => {
+                write!(f, "Invalid Ruby options (exit status {})", status)
+            }
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Configures and starts an embedded Ruby VM
+#[derive(Debug, Default)]
+pub struct VmBuilder {
+    script_name: Option<String>,
+    load_paths: Vec<String>,
+    requires: Vec<String>,
+    default_external: Option<String>,
+    default_internal: Option<String>,
+    options: Vec<String>,
+}
+
+impl VmBuilder {
+    /// Creates a builder with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the script name (`$0` and `$PROGRAM_NAME`).
+    pub fn script_name(mut self, name: &str) -> Self {
+        self.script_name = Some(name.to_string());
+        self
+    }
+
+    /// Prepends a directory to `$LOAD_PATH` (like `ruby -I`).
+    pub fn load_path(mut self, path: &str) -> Self {
+        self.load_paths.push(path.to_string());
+        self
+    }
+
+    /// Requires a library after the VM is started (like `ruby -r`).
+    pub fn require(mut self, name: &str) -> Self {
+        self.requires.push(name.to_string());
+        self
+    }
+
+    /// Sets `Encoding.default_external` (like `ruby -E external`).
+    pub fn default_external(mut self, encoding: &str) -> Self {
+        self.default_external = Some(encoding.to_string());
+        self
+    }
+
+    /// Sets `Encoding.default_internal` (like `ruby -E :internal`).
+    pub fn default_internal(mut self, encoding: &str) -> Self {
+        self.default_internal = Some(encoding.to_string());
+        self
+    }
+
+    /// Passes an interpreter option, for example `-W0`, `-v` or `--disable-gems`.
+    pub fn option(mut self, option: &str) -> Self {
+        self.options.push(option.to_string());
+        self
+    }
+
+    /// Starts the VM.
+    ///
+    /// Returns an error if the VM has already been started in this process or if the options
+    /// are invalid. Errors in options are also reported by Ruby to `stderr`, and the VM is shut
+    /// down before the error is returned, so it cannot be started again.
+    pub fn init(self) -> Result<VmGuard> {
+        if !vm::init() {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        if let Err(status) = vm::process_options(&self.arguments()) {
+            vm::cleanup();
+
+            return Err(Error::InvalidOptions(status));
+        }
+
+        if let Some(ref name) = self.script_name {
+            vm::set_script_name(name);
+        }
+
+        Ok(VmGuard { _not_send: PhantomData })
+    }
+
+    fn arguments(&self) -> Vec<String> {
+        let mut arguments = vec!["ruby".to_string()];
+
+        arguments.extend(self.options.iter().cloned());
+        arguments.extend(self.load_paths.iter().map(|path| format!("-I{}", path)));
+        arguments.extend(self.requires.iter().map(|name| format!("-r{}", name)));
+
+        if self.default_external.is_some() || self.default_internal.is_some() {
+            let external = self.default_external.as_ref().map_or("", |encoding| encoding);
+
+            match self.default_internal {
+                Some(ref internal) => arguments.push(format!("-E{}:{}", external, internal)),
+                None => arguments.push(format!("-E{}", external)),
+            }
+        }
+
+        // Options are followed by the main script, an empty one is used because
+        // the application runs Ruby code itself
+        arguments.push("-e".to_string());
+        arguments.push(String::new());
+
+        arguments
+    }
+}
+
+/// Running embedded Ruby VM
+///
+/// The VM is shut down (`ruby_cleanup()`) when the guard is dropped: `at_exit` handlers are
+/// run and Ruby objects cannot be used anymore. The VM cannot be started again in the same
+/// process.
+///
+/// The guard must be dropped by the thread which started the VM.
+pub struct VmGuard {
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for VmGuard {
+    fn drop(&mut self) {
+        vm::cleanup();
+    }
+}
@@ -1,24 +1,41 @@
 extern crate ruby_sys;
 
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde as serde_crate;
+
 mod binding;
 mod class;
 mod util;
 
 #[macro_use]
 pub mod dsl;
+pub mod embed;
 pub mod result;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod types;
 
 pub use class::any_object::AnyObject;
 pub use class::array::Array;
 pub use class::boolean::Boolean;
+pub use class::boxed_value::BoxedValue;
+pub use class::cancellation_token::CancellationToken;
 pub use class::class::Class;
+pub use class::enumerator::Enumerator;
+pub use class::exception::Exception;
+pub use class::fiber::Fiber;
 pub use class::fixnum::Fixnum;
+pub use class::gc::GcDisableGuard;
+pub use class::gvl::NoGvl;
 pub use class::hash::Hash;
+pub use class::method::{Method, UnboundMethod};
 pub use class::nil_class::NilClass;
+pub use class::rbinding::Binding;
 pub use class::rproc::Proc;
 pub use class::string::RString;
 pub use class::symbol::Symbol;
+pub use class::thread::Thread;
 pub use class::vm::VM;
 
 pub use class::traits::object::Object;
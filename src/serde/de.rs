@@ -0,0 +1,253 @@
+use std::vec;
+
+use serde_crate::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+use binding::{fixnum, float, string};
+use class::array::ArrayIterator;
+use result::{Error, Result};
+use types::ValueType;
+
+use {AnyObject, Array, Hash, Object, Symbol};
+
+/// Deserializer converting Ruby objects to Rust values
+///
+/// Usually you do not need to use it directly, see `ruru::serde::from_ruby()`.
+pub struct Deserializer {
+    object: AnyObject,
+}
+
+impl Deserializer {
+    /// Creates a new `Deserializer` reading from `object`.
+    pub fn new(object: AnyObject) -> Self {
+        Deserializer { object: object }
+    }
+
+    fn type_error(&self, expected: &str) -> Error {
+        let message = format!("Error converting {:?} to {}", self.object.ty(), expected);
+
+        Error::TypeError(message)
+    }
+
+    fn string(&self) -> Option<String> {
+        match self.object.ty() {
+            ValueType::RString => String::from_utf8(string::to_bytes(self.object.value())).ok(),
+            ValueType::Symbol => Some(unsafe { self.object.to::<Symbol>() }.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = self.object.value();
+
+        match value.ty() {
+            ValueType::Nil => visitor.visit_unit(),
+            ValueType::True => visitor.visit_bool(true),
+            ValueType::False => visitor.visit_bool(false),
+            ValueType::Fixnum => visitor.visit_i64(fixnum::fixnum_to_i64(value)),
+            ValueType::Bignum => {
+                if let Some(number) = fixnum::bignum_to_i64(value) {
+                    visitor.visit_i64(number)
+                } else if let Some(number) = fixnum::bignum_to_u64(value) {
+                    visitor.visit_u64(number)
+                } else {
+                    Err(de::Error::custom("integer out of range"))
+                }
+            }
+            ValueType::Float => visitor.visit_f64(float::num_to_float(value)),
+            ValueType::RString => {
+                let bytes = string::to_bytes(value);
+
+                match String::from_utf8(bytes) {
+                    Ok(string) => visitor.visit_string(string),
+                    Err(error) => visitor.visit_byte_buf(error.into_bytes()),
+                }
+            }
+            ValueType::Symbol => visitor.visit_string(Symbol::from(value).to_string()),
+            ValueType::Array => visitor.visit_seq(SeqAccess::new(Array::from(value))),
+            ValueType::Hash => visitor.visit_map(MapAccess::new(Hash::from(value))),
+            _ => Err(self.type_error("a serializable value")),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.object.ty() {
+            ValueType::RString => visitor.visit_byte_buf(string::to_bytes(self.object.value())),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.object.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self,
+                                                   _name: &'static str,
+                                                   visitor: V)
+                                                   -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self,
+                                         _name: &'static str,
+                                         _variants: &'static [&'static str],
+                                         visitor: V)
+                                         -> Result<V::Value> {
+        if let Some(variant) = self.string() {
+            let deserializer: de::value::StringDeserializer<Error> = variant.into_deserializer();
+
+            return visitor.visit_enum(deserializer);
+        }
+
+        if self.object.ty() == ValueType::Hash {
+            let hash = Hash::from(self.object.value());
+
+            if hash.length() == 1 {
+                let (variant, value) = hash_entries(&hash).remove(0);
+
+                return visitor.visit_enum(EnumAccess {
+                    variant: variant,
+                    value: value,
+                });
+            }
+        }
+
+        Err(self.type_error("an enum (Symbol, String or Hash with a single key)"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+fn hash_entries(hash: &Hash) -> Vec<(AnyObject, AnyObject)> {
+    let mut entries = Vec::with_capacity(hash.length());
+
+    hash.each(|key, value| entries.push((key, value)));
+
+    entries
+}
+
+struct SeqAccess {
+    iter: ArrayIterator,
+}
+
+impl SeqAccess {
+    fn new(array: Array) -> Self {
+        SeqAccess { iter: array.into_iter() }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.iter.next() {
+            Some(item) => seed.deserialize(Deserializer::new(item)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapAccess {
+    entries: vec::IntoIter<(AnyObject, AnyObject)>,
+    value: Option<AnyObject>,
+}
+
+impl MapAccess {
+    fn new(hash: Hash) -> Self {
+        MapAccess {
+            entries: hash_entries(&hash).into_iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+
+                seed.deserialize(Deserializer::new(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(Deserializer::new(value)),
+            None => Err(Error::ArgumentError("Hash value requested before its key".to_string())),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct EnumAccess {
+    variant: AnyObject,
+    value: AnyObject,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, VariantAccess)> {
+        let variant = seed.deserialize(Deserializer::new(self.variant))?;
+
+        Ok((variant, VariantAccess { value: self.value }))
+    }
+}
+
+struct VariantAccess {
+    value: AnyObject,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        if self.value.is_nil() {
+            Ok(())
+        } else {
+            Err(Deserializer::new(self.value).type_error("a unit variant (nil)"))
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(Deserializer::new(self.value))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(Deserializer::new(self.value), visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self,
+                                       _fields: &'static [&'static str],
+                                       visitor: V)
+                                       -> Result<V::Value> {
+        de::Deserializer::deserialize_map(Deserializer::new(self.value), visitor)
+    }
+}
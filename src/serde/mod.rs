@@ -0,0 +1,182 @@
+//! Conversions between Rust data structures and Ruby objects using `serde`.
+//!
+//! Available only when the `serde` feature is enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! ruru = { version = "0.8", features = ["serde"] }
+//! ```
+//!
+//! The data model is mapped to Ruby types as follows:
+//!
+//!  - `bool` to `true`/`false`;
+//!  - integers to `Fixnum` (or `Bignum` if they do not fit), floats to `Float`;
+//!  - strings, chars and bytes to `String`;
+//!  - `None`, `()` and unit structs to `nil`;
+//!  - sequences and tuples to `Array`;
+//!  - maps and structs to `Hash` (struct fields become `Symbol` or `String` keys);
+//!  - unit variants to a `Symbol` (or `String`) with the name of the variant;
+//!  - other enum variants to a `Hash` with a single `variant => value` pair.
+//!
+//! Integers are converted without loss in the whole `i64` and `u64` ranges. Converting a
+//! Ruby integer which does not fit into the target type returns an error.
+//!
+//! ```
+//! extern crate ruru;
+//!
+//! use ruru::{Fixnum, Object, VM};
+//! use ruru::serde::{from_ruby, to_ruby};
+//!
+//! fn main() {
+//!     # VM::init();
+//!     let max_i64 = to_ruby(&i64::max_value()).unwrap();
+//!     let min_i64 = to_ruby(&i64::min_value()).unwrap();
+//!     let max_u64 = to_ruby(&u64::max_value()).unwrap();
+//!
+//!     assert_eq!(from_ruby::<i64>(max_i64).unwrap(), i64::max_value());
+//!     assert_eq!(from_ruby::<i64>(min_i64).unwrap(), i64::min_value());
+//!     assert_eq!(from_ruby::<u64>(max_u64.clone()).unwrap(), u64::max_value());
+//!
+//!     let too_large = max_u64.send("+", vec![Fixnum::new(1).to_any_object()]);
+//!
+//!     assert!(from_ruby::<i64>(max_u64).is_err());
+//!     assert!(from_ruby::<u64>(too_large).is_err());
+//! }
+//! ```
+
+use std::fmt::Display;
+
+use serde_crate;
+use serde_crate::de::DeserializeOwned;
+use serde_crate::ser::Serialize;
+
+use result::{Error, Result};
+
+use AnyObject;
+
+mod de;
+mod ser;
+
+pub use self::de::Deserializer;
+pub use self::ser::{KeyStyle, Serializer};
+
+/// Converts a Rust value to a Ruby object.
+///
+/// Struct fields and unit variants are converted to `Symbol`s. Use `Serializer` with
+/// `KeyStyle::String` to get `String` keys instead.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate serde_derive;
+/// extern crate ruru;
+///
+/// use ruru::{Array, Fixnum, Hash, Object, RString, Symbol, VM};
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     host: String,
+///     port: i64,
+///     tags: Vec<String>,
+///     timeout: Option<i64>,
+/// }
+///
+/// fn main() {
+///     # VM::init();
+///     let config = Config {
+///         host: "localhost".to_string(),
+///         port: 8080,
+///         tags: vec!["web".to_string()],
+///         timeout: None,
+///     };
+///
+///     let hash = ruru::serde::to_ruby(&config).unwrap().try_convert_to::<Hash>().unwrap();
+///
+///     assert_eq!(hash.at(Symbol::new("host")).try_convert_to::<RString>().unwrap().to_string(),
+///                "localhost".to_string());
+///     assert_eq!(hash.at(Symbol::new("port")).try_convert_to::<Fixnum>(), Ok(Fixnum::new(8080)));
+///     assert_eq!(hash.at(Symbol::new("tags")).try_convert_to::<Array>().unwrap().length(), 1);
+///     assert!(hash.at(Symbol::new("timeout")).is_nil());
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// { host: 'localhost', port: 8080, tags: ['web'], timeout: nil }
+/// ```
+pub fn to_ruby<T: Serialize + ?Sized>(value: &T) -> Result<AnyObject> {
+    value.serialize(Serializer::new(KeyStyle::Symbol))
+}
+
+/// Converts a Ruby object to a Rust value.
+///
+/// Hash keys may be either `Symbol`s or `String`s.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate serde_derive;
+/// extern crate ruru;
+///
+/// use ruru::{Fixnum, Hash, Object, RString, Symbol, VM};
+///
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// enum Mode {
+///     Fast,
+///     Safe { retries: u32 },
+/// }
+///
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+///     mode: Mode,
+/// }
+///
+/// fn main() {
+///     # VM::init();
+///     let mut mode = Hash::new();
+///     let mut retries = Hash::new();
+///
+///     retries.store(Symbol::new("retries"), Fixnum::new(3));
+///     mode.store(Symbol::new("Safe"), retries);
+///
+///     let mut hash = Hash::new();
+///
+///     hash.store(RString::new("host"), RString::new("localhost"));
+///     hash.store(Symbol::new("port"), Fixnum::new(8080));
+///     hash.store(Symbol::new("mode"), mode);
+///
+///     let config: Config = ruru::serde::from_ruby(hash.to_any_object()).unwrap();
+///
+///     assert_eq!(config, Config {
+///         host: "localhost".to_string(),
+///         port: 8080,
+///         mode: Mode::Safe { retries: 3 },
+///     });
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// { 'host' => 'localhost', port: 8080, mode: { Safe: { retries: 3 } } }
+/// ```
+pub fn from_ruby<T: DeserializeOwned>(object: AnyObject) -> Result<T> {
+    T::deserialize(Deserializer::new(object))
+}
+
+impl serde_crate::ser::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Error::TypeError(message.to_string())
+    }
+}
+
+impl serde_crate::de::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Error::TypeError(message.to_string())
+    }
+}
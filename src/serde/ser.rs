@@ -0,0 +1,413 @@
+use serde_crate::ser::{self, Serialize};
+
+use binding::{fixnum, float, string};
+use result::{Error, Result};
+
+use {AnyObject, Array, Boolean, Fixnum, Hash, NilClass, Object, RString, Symbol};
+
+/// Type of keys used for struct fields and enum variants
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyStyle {
+    /// `{ name: 'value' }`
+    Symbol,
+    /// `{ 'name' => 'value' }`
+    String,
+}
+
+/// Serializer converting Rust values to Ruby objects
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate serde_derive;
+/// extern crate serde;
+/// extern crate ruru;
+///
+/// use serde::Serialize;
+///
+/// use ruru::serde::{KeyStyle, Serializer};
+/// use ruru::{Fixnum, Hash, Object, RString, VM};
+///
+/// #[derive(Serialize)]
+/// struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// fn main() {
+///     # VM::init();
+///     let point = Point { x: 1, y: 2 };
+///     let serializer = Serializer::new(KeyStyle::String);
+///
+///     let hash = point.serialize(serializer).unwrap().try_convert_to::<Hash>().unwrap();
+///
+///     assert_eq!(hash.at(RString::new("x")).try_convert_to::<Fixnum>(), Ok(Fixnum::new(1)));
+///     assert_eq!(hash.at(RString::new("y")).try_convert_to::<Fixnum>(), Ok(Fixnum::new(2)));
+/// }
+/// ```
+///
+/// Ruby:
+///
+/// ```ruby
+/// { 'x' => 1, 'y' => 2 }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Serializer {
+    key_style: KeyStyle,
+}
+
+impl Serializer {
+    /// Creates a new `Serializer` which uses `key_style` for struct fields and enum variants.
+    pub fn new(key_style: KeyStyle) -> Self {
+        Serializer { key_style: key_style }
+    }
+
+    fn key(&self, name: &str) -> AnyObject {
+        match self.key_style {
+            KeyStyle::Symbol => Symbol::new(name).to_any_object(),
+            KeyStyle::String => RString::new(name).to_any_object(),
+        }
+    }
+
+    fn tagged<T: Object>(&self, variant: &str, value: T) -> AnyObject {
+        let mut hash = Hash::new();
+
+        hash.store(self.key(variant), value);
+
+        hash.to_any_object()
+    }
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, value: bool) -> Result<AnyObject> {
+        Ok(Boolean::new(value).to_any_object())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<AnyObject> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<AnyObject> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<AnyObject> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<AnyObject> {
+        Ok(Fixnum::new(value).to_any_object())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<AnyObject> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<AnyObject> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<AnyObject> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<AnyObject> {
+        Ok(AnyObject::from(fixnum::uint_to_num(value)))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<AnyObject> {
+        self.serialize_f64(value as f64)
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<AnyObject> {
+        Ok(AnyObject::from(float::float_to_num(value)))
+    }
+
+    fn serialize_char(self, value: char) -> Result<AnyObject> {
+        let mut buffer = [0; 4];
+
+        self.serialize_str(value.encode_utf8(&mut buffer))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<AnyObject> {
+        self.serialize_bytes(value.as_bytes())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<AnyObject> {
+        Ok(AnyObject::from(string::new_from_bytes(value)))
+    }
+
+    fn serialize_none(self) -> Result<AnyObject> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<AnyObject> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<AnyObject> {
+        Ok(NilClass::new().to_any_object())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<AnyObject> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self,
+                              _name: &'static str,
+                              _variant_index: u32,
+                              variant: &'static str)
+                              -> Result<AnyObject> {
+        Ok(self.key(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self,
+                                                       _name: &'static str,
+                                                       value: &T)
+                                                       -> Result<AnyObject> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self,
+                                                        _name: &'static str,
+                                                        _variant_index: u32,
+                                                        variant: &'static str,
+                                                        value: &T)
+                                                        -> Result<AnyObject> {
+        let value = value.serialize(self)?;
+
+        Ok(self.tagged(variant, value))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(self, None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(self,
+                               _name: &'static str,
+                               _variant_index: u32,
+                               variant: &'static str,
+                               _len: usize)
+                               -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(self, Some(variant)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer> {
+        Ok(MapSerializer::new(self, None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<MapSerializer> {
+        Ok(MapSerializer::new(self, None))
+    }
+
+    fn serialize_struct_variant(self,
+                                _name: &'static str,
+                                _variant_index: u32,
+                                variant: &'static str,
+                                _len: usize)
+                                -> Result<MapSerializer> {
+        Ok(MapSerializer::new(self, Some(variant)))
+    }
+}
+
+#[doc(hidden)]
+pub struct SeqSerializer {
+    serializer: Serializer,
+    variant: Option<&'static str>,
+    array: Array,
+}
+
+impl SeqSerializer {
+    fn new(serializer: Serializer, variant: Option<&'static str>) -> Self {
+        SeqSerializer {
+            serializer: serializer,
+            variant: variant,
+            array: Array::new(),
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let value = value.serialize(self.serializer)?;
+
+        self.array.push(value);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<AnyObject> {
+        match self.variant {
+            Some(variant) => Ok(self.serializer.tagged(variant, self.array)),
+            None => Ok(self.array.to_any_object()),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+#[doc(hidden)]
+pub struct MapSerializer {
+    serializer: Serializer,
+    variant: Option<&'static str>,
+    hash: Hash,
+    next_key: Option<AnyObject>,
+}
+
+impl MapSerializer {
+    fn new(serializer: Serializer, variant: Option<&'static str>) -> Self {
+        MapSerializer {
+            serializer: serializer,
+            variant: variant,
+            hash: Hash::new(),
+            next_key: None,
+        }
+    }
+
+    fn store_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        let key = self.serializer.key(key);
+        let value = value.serialize(self.serializer)?;
+
+        self.hash.store(key, value);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<AnyObject> {
+        match self.variant {
+            Some(variant) => Ok(self.serializer.tagged(variant, self.hash)),
+            None => Ok(self.hash.to_any_object()),
+        }
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.next_key = Some(key.serialize(self.serializer)?);
+
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = match self.next_key.take() {
+            Some(key) => key,
+            None => {
+                return Err(Error::ArgumentError("Hash value serialized before its key"
+                    .to_string()))
+            }
+        };
+
+        let value = value.serialize(self.serializer)?;
+
+        self.hash.store(key, value);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self,
+                                              key: &'static str,
+                                              value: &T)
+                                              -> Result<()> {
+        self.store_field(key, value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = AnyObject;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self,
+                                              key: &'static str,
+                                              value: &T)
+                                              -> Result<()> {
+        self.store_field(key, value)
+    }
+
+    fn end(self) -> Result<AnyObject> {
+        self.finish()
+    }
+}
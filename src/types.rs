@@ -4,3 +4,40 @@ pub use ruby_sys::types::{Argc, c_char, c_int, c_long, CallbackPtr, CallbackMutP
                           InternalValue, SignedValue, Value, ValueType};
 
 pub type Callback<I, O> = extern "C" fn(Argc, *const AnyObject, I) -> O;
+
+/// Method with a fixed number of arguments
+///
+/// It is returned by the functions created with `fixed_methods!` macro,
+/// see `Object::define_fixed_method()`.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedMethod {
+    arity: Argc,
+    callback: CallbackPtr,
+}
+
+impl FixedMethod {
+    /// Creates a method from an `extern` function, it is used by `fixed_methods!` macro.
+    ///
+    /// # Safety
+    ///
+    /// `callback` must point to an `extern "C"` function which receives the receiver and then
+    /// exactly `arity` arguments as `Value`s (or types with the same representation), and
+    /// `arity` must not exceed `15`.
+    #[doc(hidden)]
+    pub unsafe fn new(arity: Argc, callback: CallbackPtr) -> Self {
+        FixedMethod {
+            arity: arity,
+            callback: callback,
+        }
+    }
+
+    /// Number of arguments the method receives (from `0` to `15`).
+    pub fn arity(&self) -> Argc {
+        self.arity
+    }
+
+    /// Pointer to the `extern` function which receives the arguments as separate parameters.
+    pub fn callback(&self) -> CallbackPtr {
+        self.callback
+    }
+}